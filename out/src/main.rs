@@ -1,29 +1,87 @@
-#![allow(unused)]
+#![allow(unused, non_snake_case)]
+
+use cello_runtime::{
+    de_edit, display, edit_alphanumeric, edit_numeric, figurative, subscript, usage, Alphanumeric,
+    Decimal, Num, Record, Rounding, Text,
+};
+
+struct Program {
+    b: Num<1>,
+    i: Num<1>,
+    return_code: Num<4, 0, true, usage::Binary, 2>,
+}
 
-use conum::{Num, NumFrom};
 fn main() {
-    let mut fact: Num<15> = Num::zero();
-    let mut n: Num<2> = Num::zero();
-    let mut i: Num<2> = Num::zero();
-    let mut ist = String::from("00");
-    let mut factst = String::from("000000000000000000");
+    let mut program = Program {
+        b: Num::zero(),
+        i: Num::zero(),
+        return_code: Num::zero(),
+    };
+    program.run();
+    program.stop_run();
+}
 
-    n = 16.into();
-    i = 0.into();
-    fact = 1.into();
-    loop {
-        if i > n {
-            break;
-        }
-        ist = i.to_zeroed_string();
-        factst = fact.to_zeroed_string();
-        println!(
-            "{}{}{}",
-            format!("{}", ist),
-            format!("{}", "! = "),
-            format!("{}", factst)
-        );
-        i += 1;
-        fact *= fact.convert(&i);
+impl Program {
+    fn run(&mut self) {
+        self.start();
+    }
+
+    fn stop_run(&self) -> ! {
+        std::process::exit(self.return_code.decimal().truncate() as i32);
+    }
+
+    fn start(&mut self) {
+        if self.b.decimal() > Decimal::from(1)
+            || (self.b.decimal() == Decimal::from(2) && !(self.i.decimal() < Decimal::from(3)))
+        {
+            display(&[&b"x"[..]]);
+        }
+        if self.b.decimal() > Decimal::from(1) || self.b.decimal() == Decimal::from(3) {
+            display(&[&b"x"[..]]);
+        }
+        if self.b.decimal() != Decimal::from(1) && self.b.decimal() != Decimal::from(2) {
+            display(&[&b"x"[..]]);
+        }
+        if !(self.b.decimal() == Decimal::from(1) || self.i.decimal() == Decimal::from(2)) {
+            display(&[&b"x"[..]]);
+        }
+        {
+            if let Some(result) = (|| {
+                Some(
+                    (Decimal::new(1, 0) + (Decimal::new(2, 0) * Decimal::new(3, 0)))
+                        - (self
+                            .i
+                            .decimal()
+                            .divide((Decimal::new(2, 0).power(Decimal::new(2, 0))?))?),
+                )
+            })() {
+                self.b.set(result);
+            }
+        }
+        {
+            let result = (Decimal::new(1, 0) + Decimal::new(2, 0)) * (-self.i.decimal());
+            self.b.set(result);
+        }
+        {
+            let addend = (Decimal::from(1) + Decimal::from(2));
+            self.b.set(self.b.decimal() + addend);
+            self.i.set(self.i.decimal() + addend);
+        }
+        {
+            let sum = (self.b.decimal() + Decimal::from(2)) + self.i.decimal();
+            self.b.set(sum);
+        }
+        {
+            let multiplier = Decimal::from(2);
+            self.b.set(multiplier * self.b.decimal());
+            self.i.set(multiplier * self.i.decimal());
+        }
+        {
+            let multiplier = self.b.decimal();
+            let product = multiplier * Decimal::from(3);
+            self.i.set(product);
+            self.b.set(product);
+        }
+        self.stop_run();
     }
 }
@@ -0,0 +1,465 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul, Neg, Sub};
+
+// how many digits after the decimal point a quotient is worked out to, which is more than any
+// field can hold. products are cut back to this as well, so a long computation does not keep
+// growing its fraction
+const QUOTIENT_SCALE: u32 = 18;
+
+// how a result with more digits after the decimal point than its receiver is brought down to fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    AwayFromZero,
+    NearestAwayFromZero,
+    NearestEven,
+    NearestTowardZero,
+    // rounding is a size error, unless the dropped digits are all zero
+    Prohibited,
+    TowardGreater,
+    TowardLesser,
+    Truncation,
+}
+
+// an intermediate result of arithmetic, kept exact until it is stored into a field. value holds
+// the digits and scale how many of them are after the decimal point
+#[derive(Debug, Clone, Copy)]
+pub struct Decimal {
+    value: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    pub fn new(value: i128, scale: u32) -> Self {
+        Self { value, scale }
+    }
+
+    pub fn zero() -> Self {
+        Self::new(0, 0)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.value < 0
+    }
+
+    // the digits before the decimal point, anything after it is dropped
+    pub fn truncate(&self) -> i128 {
+        self.value / 10i128.pow(self.scale)
+    }
+
+    // the digits up to scale places after the decimal point, anything after that is dropped. a
+    // negative scale drops digits before the decimal point as well, as a pic 999pp field does
+    pub fn truncate_to(&self, scale: i32) -> Self {
+        self.round_to(scale, Rounding::Truncation).unwrap()
+    }
+
+    // the quotient to QUOTIENT_SCALE places after the decimal point, or none when dividing by zero.
+    // the digits are worked out one at a time, so only the remainder has to fit next to the divisor
+    pub fn divide(self, divisor: Self) -> Option<Self> {
+        if divisor.is_zero() {
+            return None;
+        }
+
+        let (dividend, divisor, _) = self.align(divisor);
+        let mut value = dividend / divisor;
+        let mut remainder = dividend % divisor;
+        for _ in 0..QUOTIENT_SCALE {
+            remainder *= 10;
+            value = value * 10 + remainder / divisor;
+            remainder %= divisor;
+        }
+
+        Some(Self::new(value, QUOTIENT_SCALE))
+    }
+
+    // none when the mode prohibits rounding and there are digits that would be lost
+    pub fn round_to(&self, scale: i32, rounding: Rounding) -> Option<Self> {
+        if scale >= self.scale as i32 {
+            return Some(*self);
+        }
+
+        let value = self.digits_at(scale, rounding)?;
+        if scale < 0 {
+            return Some(Self::new(value * 10i128.pow(scale.unsigned_abs()), 0));
+        }
+
+        Some(Self::new(value, scale as u32))
+    }
+
+    // the value counted in units of 10^-scale, with the digits past that rounded away
+    pub fn digits_at(&self, scale: i32, rounding: Rounding) -> Option<i128> {
+        if scale >= self.scale as i32 {
+            return Some(self.rescale(scale as u32));
+        }
+
+        let divisor = 10i128.pow((self.scale as i32 - scale) as u32);
+        let truncated = self.value / divisor;
+        let dropped = (self.value % divisor).abs();
+        if dropped == 0 {
+            return Some(truncated);
+        }
+
+        let away = truncated + self.value.signum();
+        let half = (dropped * 2).cmp(&divisor);
+        let value = match rounding {
+            Rounding::AwayFromZero => away,
+            Rounding::NearestAwayFromZero if half.is_ge() => away,
+            Rounding::NearestTowardZero if half.is_gt() => away,
+            Rounding::NearestEven if half.is_gt() || half.is_eq() && truncated % 2 != 0 => away,
+            Rounding::TowardGreater if self.value > 0 => away,
+            Rounding::TowardLesser if self.value < 0 => away,
+            Rounding::Prohibited => return None,
+            _ => truncated,
+        };
+
+        Some(value)
+    }
+
+    // an integer exponent is worked out exactly by repeated squaring, anything else goes through
+    // floating point. none when the result is undefined (zero to a power that isn't positive, or a
+    // negative number to a fractional one) or too large to hold
+    pub fn power(self, exponent: Self) -> Option<Self> {
+        if exponent.value % 10i128.pow(exponent.scale) != 0 {
+            if self.is_negative() {
+                return None;
+            }
+            let result = self.to_f64().powf(exponent.to_f64());
+            return Self::from_f64(result);
+        }
+
+        let exponent = exponent.truncate();
+        if self.is_zero() && exponent <= 0 {
+            return None;
+        }
+
+        let mut result = Self::new(1, 0);
+        let mut base = self;
+        let mut remaining = exponent.unsigned_abs();
+        while remaining > 0 {
+            if remaining % 2 == 1 {
+                result = result.checked_mul(base)?;
+            }
+            remaining /= 2;
+            if remaining > 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+
+        if exponent < 0 {
+            Self::new(1, 0).divide(result)
+        } else {
+            Some(result)
+        }
+    }
+
+    // when the exact product does not fit, low order fraction digits of the operands are dropped
+    // until it does, the digits before the decimal point are never given up
+    fn checked_mul(self, other: Self) -> Option<Self> {
+        let (mut left, mut right) = (self, other);
+        loop {
+            if let Some(value) = left.value.checked_mul(right.value) {
+                let product = Self::new(value, left.scale + right.scale);
+                return Some(product.truncate_to(QUOTIENT_SCALE as i32));
+            }
+
+            if left.scale == 0 && right.scale == 0 {
+                return None;
+            }
+            if left.scale >= right.scale {
+                left = left.truncate_to(left.scale as i32 - 1);
+            } else {
+                right = right.truncate_to(right.scale as i32 - 1);
+            }
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        self.value as f64 / 10f64.powi(self.scale as i32)
+    }
+
+    fn from_f64(value: f64) -> Option<Self> {
+        let scaled = (value * 10f64.powi(QUOTIENT_SCALE as i32)).trunc();
+        if !scaled.is_finite() || scaled.abs() >= i128::MAX as f64 {
+            return None;
+        }
+
+        Some(Self::new(scaled as i128, QUOTIENT_SCALE))
+    }
+
+    fn rescale(&self, scale: u32) -> i128 {
+        self.value * 10i128.pow(scale - self.scale)
+    }
+
+    // both values brought to the larger of the two scales
+    fn align(self, other: Self) -> (i128, i128, u32) {
+        let scale = self.scale.max(other.scale);
+        (self.rescale(scale), other.rescale(scale), scale)
+    }
+}
+
+impl From<i32> for Decimal {
+    fn from(value: i32) -> Self {
+        Self::new(value as i128, 0)
+    }
+}
+
+impl Add for Decimal {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (left, right, scale) = self.align(other);
+        Self::new(left + right, scale)
+    }
+}
+
+impl Sub for Decimal {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        let (left, right, scale) = self.align(other);
+        Self::new(left - right, scale)
+    }
+}
+
+impl Mul for Decimal {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.checked_mul(other).expect("arithmetic overflow")
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.value, self.scale)
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (left, right, _) = self.align(*other);
+        left.cmp(&right)
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let digits = format!(
+            "{:0width$}",
+            self.value.abs(),
+            width = self.scale as usize + 1
+        );
+        let (whole, fraction) = digits.split_at(digits.len() - self.scale as usize);
+        let sign = if self.value < 0 { "-" } else { "" };
+        if fraction.is_empty() {
+            write!(f, "{sign}{whole}")
+        } else {
+            write!(f, "{sign}{whole}.{fraction}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decimal::{Decimal, Rounding};
+
+    fn decimal(value: i128, scale: u32) -> Decimal {
+        Decimal::new(value, scale)
+    }
+
+    fn rounded(value: i128, scale: u32, rounding: Rounding) -> Option<Decimal> {
+        decimal(value, scale).round_to(0, rounding)
+    }
+
+    #[test]
+    fn test_divide() {
+        assert_eq!(
+            Decimal::from(10).divide(Decimal::from(4)),
+            Some(decimal(25, 1))
+        );
+        assert_eq!(
+            Decimal::from(-7).divide(Decimal::from(2)),
+            Some(decimal(-35, 1))
+        );
+        assert_eq!(
+            Decimal::from(1).divide(Decimal::from(3)),
+            Some(decimal(333_333_333_333_333_333, 18))
+        );
+        assert_eq!(
+            decimal(15, 1).divide(decimal(5, 2)),
+            Some(Decimal::from(30))
+        );
+    }
+
+    #[test]
+    fn test_divide_by_zero() {
+        assert_eq!(Decimal::from(1).divide(Decimal::zero()), None);
+    }
+
+    #[test]
+    fn test_away_from_zero() {
+        assert_eq!(
+            rounded(24, 1, Rounding::AwayFromZero),
+            Some(Decimal::from(3))
+        );
+        assert_eq!(
+            rounded(-24, 1, Rounding::AwayFromZero),
+            Some(Decimal::from(-3))
+        );
+        assert_eq!(
+            rounded(20, 1, Rounding::AwayFromZero),
+            Some(Decimal::from(2))
+        );
+    }
+
+    #[test]
+    fn test_nearest_away_from_zero() {
+        assert_eq!(
+            rounded(24, 1, Rounding::NearestAwayFromZero),
+            Some(Decimal::from(2))
+        );
+        assert_eq!(
+            rounded(25, 1, Rounding::NearestAwayFromZero),
+            Some(Decimal::from(3))
+        );
+        assert_eq!(
+            rounded(-25, 1, Rounding::NearestAwayFromZero),
+            Some(Decimal::from(-3))
+        );
+    }
+
+    #[test]
+    fn test_nearest_even() {
+        assert_eq!(
+            rounded(25, 1, Rounding::NearestEven),
+            Some(Decimal::from(2))
+        );
+        assert_eq!(
+            rounded(35, 1, Rounding::NearestEven),
+            Some(Decimal::from(4))
+        );
+        assert_eq!(
+            rounded(251, 2, Rounding::NearestEven),
+            Some(Decimal::from(3))
+        );
+        assert_eq!(
+            rounded(-25, 1, Rounding::NearestEven),
+            Some(Decimal::from(-2))
+        );
+    }
+
+    #[test]
+    fn test_nearest_toward_zero() {
+        assert_eq!(
+            rounded(25, 1, Rounding::NearestTowardZero),
+            Some(Decimal::from(2))
+        );
+        assert_eq!(
+            rounded(26, 1, Rounding::NearestTowardZero),
+            Some(Decimal::from(3))
+        );
+        assert_eq!(
+            rounded(-25, 1, Rounding::NearestTowardZero),
+            Some(Decimal::from(-2))
+        );
+    }
+
+    #[test]
+    fn test_toward_greater() {
+        assert_eq!(
+            rounded(21, 1, Rounding::TowardGreater),
+            Some(Decimal::from(3))
+        );
+        assert_eq!(
+            rounded(-29, 1, Rounding::TowardGreater),
+            Some(Decimal::from(-2))
+        );
+    }
+
+    #[test]
+    fn test_toward_lesser() {
+        assert_eq!(
+            rounded(29, 1, Rounding::TowardLesser),
+            Some(Decimal::from(2))
+        );
+        assert_eq!(
+            rounded(-21, 1, Rounding::TowardLesser),
+            Some(Decimal::from(-3))
+        );
+    }
+
+    #[test]
+    fn test_truncation() {
+        assert_eq!(rounded(29, 1, Rounding::Truncation), Some(Decimal::from(2)));
+        assert_eq!(
+            rounded(-29, 1, Rounding::Truncation),
+            Some(Decimal::from(-2))
+        );
+    }
+
+    #[test]
+    fn test_prohibited() {
+        assert_eq!(rounded(25, 1, Rounding::Prohibited), None);
+        assert_eq!(
+            rounded(200, 2, Rounding::Prohibited),
+            Some(Decimal::from(2))
+        );
+    }
+
+    #[test]
+    fn test_round_to_negative_scale() {
+        let value = Decimal::from(12350);
+        assert_eq!(
+            value.round_to(-2, Rounding::NearestAwayFromZero),
+            Some(Decimal::from(12400))
+        );
+        assert_eq!(value.truncate_to(-2), Decimal::from(12300));
+    }
+
+    #[test]
+    fn test_power() {
+        assert_eq!(
+            Decimal::from(2).power(Decimal::from(10)),
+            Some(Decimal::from(1024))
+        );
+        assert_eq!(
+            decimal(15, 1).power(Decimal::from(2)),
+            Some(decimal(225, 2))
+        );
+        assert_eq!(
+            Decimal::from(2).power(Decimal::from(-2)),
+            Some(decimal(25, 2))
+        );
+        assert_eq!(
+            Decimal::from(4).power(decimal(5, 1)),
+            Some(Decimal::from(2))
+        );
+    }
+
+    #[test]
+    fn test_power_undefined() {
+        assert_eq!(Decimal::zero().power(Decimal::zero()), None);
+        assert_eq!(Decimal::from(-8).power(decimal(5, 1)), None);
+        assert_eq!(Decimal::from(10).power(Decimal::from(40)), None);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{Decimal, Rounding};
+
+// the text a numeric edited field holds once value is moved into it. the picture is the expanded,
+// lowercase picture string, so zz9 is written out as zz9 and z(3) as zzz
+pub fn edit_numeric(value: Decimal, picture: &str) -> String {
+    let symbols: Vec<char> = picture.chars().collect();
+    let (floating, is_digit) = digit_positions(&symbols);
+
+    let count = is_digit.iter().filter(|d| **d).count();
+    let scale = match symbols.iter().position(|c| matches!(c, '.' | 'v')) {
+        Some(point) => is_digit[point..].iter().filter(|d| **d).count(),
+        None => 0,
+    };
+    let digits = value
+        .digits_at(scale as i32, Rounding::Truncation)
+        .unwrap()
+        .unsigned_abs()
+        % 10u128.pow(count as u32);
+    let negative = value.is_negative() && digits != 0;
+    let digits = format!("{:0count$}", digits);
+
+    // check protection fills whatever is suppressed with asterisks instead of spaces
+    let fill = if symbols.contains(&'*') { '*' } else { ' ' };
+    if digits.bytes().all(|b| b == b'0') && !symbols.contains(&'9') {
+        return symbols
+            .iter()
+            .filter(|c| **c != 'v')
+            .map(|c| if *c == '.' && fill == '*' { '.' } else { fill })
+            .collect();
+    }
+
+    let sign = |symbol: char| match symbol {
+        '+' if negative => '-',
+        '-' if !negative => ' ',
+        symbol => symbol,
+    };
+    let mut digits = digits.chars();
+    let mut edited: Vec<char> = vec![];
+    // suppression ends at the first digit that is not a leading zero, which a 9 or the decimal
+    // point always is. a floating symbol lands right before it
+    let mut significant = false;
+    let begin = |edited: &mut Vec<char>, significant: &mut bool| {
+        if !*significant {
+            if let (Some(floating), Some(last)) = (floating, edited.last_mut()) {
+                *last = sign(floating);
+            }
+            *significant = true;
+        }
+    };
+
+    let mut i = 0;
+    while i < symbols.len() {
+        let c = symbols[i];
+        match c {
+            'c' | 'd' => {
+                let text = if c == 'c' { "CR" } else { "DB" };
+                edited.extend(if negative { text.chars() } else { "  ".chars() });
+                i += 1;
+            }
+            'v' => {}
+            '.' => {
+                begin(&mut edited, &mut significant);
+                edited.push('.');
+            }
+            _ if is_digit[i] => {
+                let digit = digits.next().unwrap();
+                if digit != '0' || c == '9' {
+                    begin(&mut edited, &mut significant);
+                }
+                edited.push(match c {
+                    _ if significant => digit,
+                    '*' => '*',
+                    _ => ' ',
+                });
+            }
+            _ if Some(c) == floating => edited.push(' '),
+            '+' | '-' => edited.push(sign(c)),
+            '$' => edited.push('$'),
+            _ => {
+                let insertion = if c == 'b' { ' ' } else { c };
+                let suppressed = if floating.is_some() { ' ' } else { fill };
+                edited.push(if significant { insertion } else { suppressed });
+            }
+        }
+        i += 1;
+    }
+
+    edited.into_iter().collect()
+}
+
+// the number an edited field shows, read back by the picture it was edited with, which is what
+// moving it into a numeric field does. the digits are the ones in digit positions, suppressed ones
+// counting as zeros, and it is negative when it shows a minus sign, cr or db
+pub fn de_edit(text: &[u8], picture: &str) -> Decimal {
+    let symbols: Vec<char> = picture.chars().collect();
+    let (_, is_digit) = digit_positions(&symbols);
+
+    // every symbol but v takes one character of the text
+    let mut characters = text.iter();
+    let mut value = 0;
+    let mut scale = 0;
+    let mut point = false;
+    for (symbol, is_digit) in symbols.into_iter().zip(is_digit) {
+        if symbol == 'v' {
+            point = true;
+            continue;
+        }
+        let character = characters.next().copied().unwrap_or(b' ');
+        point |= symbol == '.';
+        if is_digit {
+            let digit = if character.is_ascii_digit() {
+                character - b'0'
+            } else {
+                0
+            };
+            value = value * 10 + digit as i128;
+            scale += point as u32;
+        }
+    }
+
+    let negative = text.contains(&b'-') || text.windows(2).any(|w| w == b"CR" || w == b"DB");
+    Decimal::new(if negative { -value } else { value }, scale)
+}
+
+// which of the symbols of a numeric edited picture hold digits, along with its floating symbol. a
+// $, + or - that appears more than once floats: the first of them only holds the symbol and the
+// rest are digit positions that it can move into
+fn digit_positions(symbols: &[char]) -> (Option<char>, Vec<bool>) {
+    let floating = ['$', '+', '-']
+        .into_iter()
+        .find(|s| symbols.iter().filter(|c| *c == s).count() > 1);
+    let first_floating = floating.and_then(|f| symbols.iter().position(|c| *c == f));
+    let is_digit = symbols
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            matches!(c, '9' | 'z' | '*') || Some(*c) == floating && Some(i) != first_floating
+        })
+        .collect();
+    (floating, is_digit)
+}
+
+// the text an alphanumeric edited field holds once value is moved into it, with the characters of
+// value filling the x, a and 9 positions from the left and spaces once it runs out
+pub fn edit_alphanumeric(value: &[u8], picture: &str) -> String {
+    let mut characters = value.iter();
+    picture
+        .chars()
+        .map(|c| match c {
+            'b' => ' ',
+            '0' | '/' => c,
+            _ => characters.next().map_or(' ', |b| *b as char),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::edit::{de_edit, edit_alphanumeric, edit_numeric};
+    use crate::Decimal;
+
+    #[test]
+    fn test_floating_currency() {
+        let picture = "$$$,$$9.99cr";
+        assert_eq!(
+            edit_numeric(Decimal::new(123450, 2), picture),
+            " $1,234.50  "
+        );
+        assert_eq!(edit_numeric(Decimal::from(-5), picture), "     $5.00CR");
+    }
+
+    #[test]
+    fn test_zero_suppression() {
+        let picture = "zz/zz/9999";
+        assert_eq!(edit_numeric(Decimal::from(12252023), picture), "12/25/2023");
+        assert_eq!(edit_numeric(Decimal::from(1012023), picture), " 1/01/2023");
+    }
+
+    #[test]
+    fn test_check_protection() {
+        let picture = "***,**9.99";
+        assert_eq!(edit_numeric(Decimal::new(1250, 2), picture), "*****12.50");
+        assert_eq!(
+            edit_numeric(Decimal::new(23456789, 2), picture),
+            "234,567.89"
+        );
+        assert_eq!(edit_numeric(Decimal::zero(), picture), "******0.00");
+    }
+
+    #[test]
+    fn test_all_suppressed() {
+        assert_eq!(edit_numeric(Decimal::zero(), "zzz"), "   ");
+        assert_eq!(edit_numeric(Decimal::zero(), "***.**"), "***.**");
+    }
+
+    #[test]
+    fn test_sign() {
+        assert_eq!(edit_numeric(Decimal::from(-42), "+999"), "-042");
+        assert_eq!(edit_numeric(Decimal::from(42), "+999"), "+042");
+        assert_eq!(edit_numeric(Decimal::from(42), "999-"), "042 ");
+        assert_eq!(edit_numeric(Decimal::from(-7), "---9"), "  -7");
+    }
+
+    #[test]
+    fn test_de_edit() {
+        let picture = "$$$,$$9.99cr";
+        assert_eq!(de_edit(b" $1,234.50  ", picture), Decimal::new(123450, 2));
+        assert_eq!(de_edit(b"     $5.00CR", picture), Decimal::from(-5));
+        assert_eq!(
+            de_edit(b"12/25/2023", "zz/zz/9999"),
+            Decimal::from(12252023)
+        );
+        assert_eq!(de_edit(b"*****12.50", "***,**9.99"), Decimal::new(1250, 2));
+        assert_eq!(de_edit(b"  -7", "---9"), Decimal::from(-7));
+    }
+
+    #[test]
+    fn test_edit_alphanumeric() {
+        assert_eq!(edit_alphanumeric(b"ab12", "xxbxx"), "ab 12");
+        assert_eq!(edit_alphanumeric(b"2023", "99/99"), "20/23");
+        assert_eq!(edit_alphanumeric(b"a", "xx0x"), "a 0 ");
+    }
+}
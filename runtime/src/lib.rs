@@ -0,0 +1,13 @@
+// the types the generated programs are built on
+mod decimal;
+mod edit;
+mod num;
+mod record;
+mod table;
+pub mod usage;
+
+pub use decimal::{Decimal, Rounding};
+pub use edit::{de_edit, edit_alphanumeric, edit_numeric};
+pub use num::Num;
+pub use record::{display, figurative, Alphanumeric, Record, Text};
+pub use table::subscript;
@@ -0,0 +1,158 @@
+use crate::usage::{self, Usage};
+use crate::{Decimal, Record, Rounding};
+
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+
+// a numeric field of N digits, SCALE of which are after the implied decimal point, like a pic
+// s9(N-SCALE)v9(SCALE) field. a scale larger than N puts zeros between the point and the digits
+// (pic ppp999) and a negative one puts them between the digits and the point (pic 999pp). storing
+// into it keeps only the digits that fit, and drops the sign unless the field is SIGNED. U is its
+// usage, which decides how the value is laid out in the BYTES bytes the field takes
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy)]
+pub struct Num<
+    const N: usize,
+    const SCALE: i32 = 0,
+    const SIGNED: bool = false,
+    U: Usage = usage::Display,
+    const BYTES: usize = N,
+> {
+    bytes: [u8; BYTES],
+    usage: PhantomData<U>,
+}
+
+impl<const N: usize, const SCALE: i32, const SIGNED: bool, U: Usage, const BYTES: usize>
+    Num<N, SCALE, SIGNED, U, BYTES>
+{
+    pub fn zero() -> Self {
+        assert_eq!(
+            U::size(N),
+            BYTES,
+            "a {N} digit field does not take {BYTES} bytes"
+        );
+        let mut num = Self {
+            bytes: [0; BYTES],
+            usage: PhantomData,
+        };
+        num.store(0);
+        num
+    }
+
+    pub fn decimal(&self) -> Decimal {
+        let value = self.value();
+        if SCALE < 0 {
+            Decimal::new(value * 10i128.pow(SCALE.unsigned_abs()), 0)
+        } else {
+            Decimal::new(value, SCALE as u32)
+        }
+    }
+
+    pub fn set(&mut self, value: Decimal) {
+        let digits = value.digits_at(SCALE, Rounding::Truncation).unwrap();
+        self.store(digits % U::limit(N, SIGNED));
+    }
+
+    // rounding that is prohibited truncates instead, the size error it would be is only noticed
+    // by a checked store
+    pub fn set_rounded(&mut self, value: Decimal, rounding: Rounding) {
+        match value.digits_at(SCALE, rounding) {
+            Some(digits) => self.store(digits % U::limit(N, SIGNED)),
+            None => self.set(value),
+        }
+    }
+
+    // a checked store, which leaves the field alone and returns false when the value has more
+    // digits before the decimal point than the field
+    pub fn try_set(&mut self, value: Decimal) -> bool {
+        self.try_set_rounded(value, Rounding::Truncation)
+    }
+
+    pub fn try_set_rounded(&mut self, value: Decimal, rounding: Rounding) -> bool {
+        match value.digits_at(SCALE, rounding) {
+            Some(digits) if digits.abs() < U::limit(N, SIGNED) => {
+                self.store(digits);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // the digits alone, without a sign or decimal point, which is what a move to an alphanumeric
+    // field takes. the scaling positions of a pic 999pp field are sent along as zeros
+    pub fn to_zeroed_string(&self) -> String {
+        let scaling = if SCALE < 0 { SCALE.unsigned_abs() } else { 0 };
+        format!("{}{}", self.digits(), "0".repeat(scaling as usize))
+    }
+
+    // the N digits the field stores
+    fn digits(&self) -> String {
+        format!("{:0width$}", self.value().unsigned_abs(), width = N)
+    }
+
+    // whether the field holds a valid value for its usage, rather than bytes some group move or
+    // redefinition left in it
+    pub fn is_numeric(&self) -> bool {
+        U::is_numeric(&self.bytes, SIGNED)
+    }
+
+    fn value(&self) -> i128 {
+        U::decode(&self.bytes, SIGNED)
+    }
+
+    fn store(&mut self, digits: i128) {
+        let digits = if SIGNED { digits } else { digits.abs() };
+        self.bytes.copy_from_slice(&U::encode(digits, N, SIGNED));
+    }
+}
+
+// SAFETY: a num is nothing but its bytes, the usage marker is zero sized
+unsafe impl<const N: usize, const SCALE: i32, const SIGNED: bool, U: Usage, const BYTES: usize>
+    Record for Num<N, SCALE, SIGNED, U, BYTES>
+{
+}
+
+impl<const N: usize, const SCALE: i32, const SIGNED: bool, U: Usage, const BYTES: usize> From<i32>
+    for Num<N, SCALE, SIGNED, U, BYTES>
+{
+    fn from(value: i32) -> Self {
+        let mut num = Self::zero();
+        num.set(Decimal::from(value));
+        num
+    }
+}
+
+impl<const N: usize, const SCALE: i32, const SIGNED: bool, U: Usage, const BYTES: usize>
+    From<Decimal> for Num<N, SCALE, SIGNED, U, BYTES>
+{
+    fn from(value: Decimal) -> Self {
+        let mut num = Self::zero();
+        num.set(value);
+        num
+    }
+}
+
+// displayed with its sign when it has one, and with the decimal point and any scaling zeros
+// where they are implied
+impl<const N: usize, const SCALE: i32, const SIGNED: bool, U: Usage, const BYTES: usize> Display
+    for Num<N, SCALE, SIGNED, U, BYTES>
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if SIGNED {
+            write!(f, "{}", if self.value() < 0 { '-' } else { '+' })?;
+        }
+
+        let digits = self.digits();
+        if SCALE <= 0 {
+            return write!(f, "{digits}{}", "0".repeat(SCALE.unsigned_abs() as usize));
+        }
+
+        let scale = SCALE as usize;
+        if scale > N {
+            write!(f, ".{}{digits}", "0".repeat(scale - N))
+        } else {
+            let (whole, fraction) = digits.split_at(N - scale);
+            write!(f, "{whole}.{fraction}")
+        }
+    }
+}
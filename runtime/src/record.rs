@@ -0,0 +1,198 @@
+use crate::Decimal;
+
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+
+/// storage that is nothing but bytes, which every field and group is. a group move copies these
+/// bytes whatever the fields inside of them are
+///
+/// # Safety
+///
+/// implementors have to be made up of byte arrays and other records only, laid out with repr(C) or
+/// repr(transparent), so that they have no padding, an alignment of one, and any bytes are a valid
+/// value
+pub unsafe trait Record: Sized {
+    fn bytes(&self) -> &[u8] {
+        let start = (self as *const Self).cast::<u8>();
+        // SAFETY: the implementor promises to be size_of::<Self>() plain bytes
+        unsafe { std::slice::from_raw_parts(start, size_of::<Self>()) }
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        let start = (self as *mut Self).cast::<u8>();
+        // SAFETY: as above, and any bytes written through the slice are a valid value
+        unsafe { std::slice::from_raw_parts_mut(start, size_of::<Self>()) }
+    }
+
+    // the first length bytes of the record, which are all that is in use of a group that ends with
+    // a table with occurs depending on
+    fn bytes_to(&self, length: usize) -> &[u8] {
+        &self.bytes()[..length]
+    }
+
+    // an alphanumeric move into the record, which fills it from the left and cuts off what does not
+    // fit or pads with spaces what is left over
+    fn set_text(&mut self, text: &[u8]) {
+        let bytes = self.bytes_mut();
+        let length = text.len().min(bytes.len());
+        bytes[..length].copy_from_slice(&text[..length]);
+        bytes[length..].fill(b' ');
+    }
+
+    // an alphanumeric move into a field that is justified right, which fills it from the right and
+    // cuts off what does not fit on the left
+    fn set_justified(&mut self, text: &[u8]) {
+        let bytes = self.bytes_mut();
+        let length = text.len().min(bytes.len());
+        let (padding, justified) = bytes.split_at_mut(bytes.len() - length);
+        justified.copy_from_slice(&text[text.len() - length..]);
+        padding.fill(b' ');
+    }
+
+    // every byte of the record set by repeating pattern, which is what moving a figurative
+    // constant or an all literal into it does
+    fn fill(&mut self, pattern: &[u8]) {
+        self.bytes_mut()
+            .copy_from_slice(&figurative(pattern, size_of::<Self>()));
+    }
+
+    // the record with text moved into it, or filled with pattern, which is how a value clause
+    // sets it up
+    fn with_text(mut self, text: &[u8]) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    fn filled(mut self, pattern: &[u8]) -> Self {
+        self.fill(pattern);
+        self
+    }
+
+    fn alphanumeric(&self) -> Alphanumeric<'_> {
+        Alphanumeric(self.bytes())
+    }
+
+    // the bytes of the record from offset on, looked at as another record. this is how an item
+    // that redefines or renames others shares their storage
+    fn view<T: Record>(&self, offset: usize) -> &T {
+        let bytes = &self.bytes()[offset..offset + size_of::<T>()];
+        // SAFETY: the bytes are in bounds, and any bytes are a valid T, whose alignment is one
+        unsafe { &*bytes.as_ptr().cast::<T>() }
+    }
+
+    fn view_mut<T: Record>(&mut self, offset: usize) -> &mut T {
+        let bytes = &mut self.bytes_mut()[offset..offset + size_of::<T>()];
+        // SAFETY: as above
+        unsafe { &mut *bytes.as_mut_ptr().cast::<T>() }
+    }
+}
+
+// pattern repeated for length bytes, the last repetition cut short when it does not fit
+pub fn figurative(pattern: &[u8], length: usize) -> Vec<u8> {
+    pattern.iter().copied().cycle().take(length).collect()
+}
+
+// what a display statement writes, which is the bytes of its operands one after the other as they
+// are, not text, and then the end of the line
+pub fn display(operands: &[&[u8]]) {
+    let mut line = operands.concat();
+    line.push(b'\n');
+    std::io::stdout()
+        .write_all(&line)
+        .expect("cannot write to standard output");
+}
+
+// an alphanumeric field of N characters
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy)]
+pub struct Text<const N: usize> {
+    bytes: [u8; N],
+}
+
+impl<const N: usize> Text<N> {
+    pub fn spaces() -> Self {
+        Self { bytes: [b' '; N] }
+    }
+}
+
+// SAFETY: a text is nothing but its bytes
+unsafe impl<const N: usize> Record for Text<N> {}
+
+impl<const N: usize> Display for Text<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.alphanumeric())
+    }
+}
+
+// the contents of a field as they are compared alphanumerically, where the shorter of two values
+// is padded with spaces to the length of the longer one
+#[derive(Debug, Clone, Copy)]
+pub struct Alphanumeric<'a>(&'a [u8]);
+
+impl<'a> Alphanumeric<'a> {
+    // the contents without their trailing spaces
+    pub fn trim_end(&self) -> &'a [u8] {
+        let end = self.0.iter().rposition(|byte| *byte != b' ');
+        &self.0[..end.map_or(0, |end| end + 1)]
+    }
+
+    // the contents read as an unsigned integer, which is what moving them into a numeric field
+    // does. anything but a digit counts as a zero, and only the low order digits that a decimal
+    // can hold are kept
+    pub fn decimal(&self) -> Decimal {
+        let digits = &self.0[self.0.len().saturating_sub(38)..];
+        let value = digits.iter().fold(0, |value, byte| {
+            let digit = if byte.is_ascii_digit() {
+                byte - b'0'
+            } else {
+                0
+            };
+            value * 10 + digit as i128
+        });
+        Decimal::new(value, 0)
+    }
+}
+
+impl<'a> From<&'a str> for Alphanumeric<'a> {
+    fn from(text: &'a str) -> Self {
+        Self(text.as_bytes())
+    }
+}
+
+impl<'a> From<&'a [u8]> for Alphanumeric<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl PartialEq for Alphanumeric<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Alphanumeric<'_> {}
+
+impl PartialOrd for Alphanumeric<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Alphanumeric<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let length = self.0.len().max(other.0.len());
+        let padded = |bytes: &'_ [u8]| {
+            let padding = std::iter::repeat_n(b' ', length - bytes.len());
+            bytes.iter().copied().chain(padding).collect::<Vec<u8>>()
+        };
+        padded(self.0).cmp(&padded(other.0))
+    }
+}
+
+impl Display for Alphanumeric<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(self.0))
+    }
+}
@@ -0,0 +1,13 @@
+use crate::{Decimal, Record};
+
+// SAFETY: an array of records is its records one after another, without any padding between them
+unsafe impl<T: Record, const N: usize> Record for [T; N] {}
+
+// the entry a subscript refers to, counting from zero. a subscript has to be between one and the
+// number of entries the table has in use, anything else is an error the program cannot go on from
+pub fn subscript(value: Decimal, count: Decimal) -> usize {
+    if value < Decimal::from(1) || value > count {
+        panic!("subscript {value} is out of range, the table has {count} entries");
+    }
+    value.truncate() as usize - 1
+}
@@ -0,0 +1,388 @@
+// how a numeric field is laid out in storage. it only matters once the bytes of the field are read
+// or written, arithmetic is the same whatever the usage
+pub trait Usage {
+    // how many bytes a field of that many digits takes
+    fn size(digits: usize) -> usize;
+
+    // one more than the largest magnitude the field can hold
+    fn limit(digits: usize, _signed: bool) -> i128 {
+        10i128.pow(digits as u32)
+    }
+
+    fn encode(value: i128, digits: usize, signed: bool) -> Vec<u8>;
+
+    fn decode(bytes: &[u8], signed: bool) -> i128;
+
+    // whether the bytes hold a valid value, which is what a numeric class test checks. any bytes
+    // are an integer in binary
+    fn is_numeric(_bytes: &[u8], _signed: bool) -> bool {
+        true
+    }
+}
+
+// one character per digit, with the sign of a negative value overpunched on the last of them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Display;
+
+// display with the sign overpunched on the first digit instead of the last, as sign is leading
+// has it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LeadingSign;
+
+// display with the sign in a character of its own, + or -, before or after the digits, as sign is
+// leading separate and sign is trailing separate have it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LeadingSeparate;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TrailingSeparate;
+
+// a big-endian two's complement integer, as wide as the digits need
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Binary;
+
+// two digits per byte, with the sign in the low nibble of the last one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PackedDecimal;
+
+// an integer in the byte order of the machine, which holds whatever fits its bytes rather than
+// only as many digits as its picture has
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NativeBinary;
+
+const POSITIVE: u8 = 0xc;
+const NEGATIVE: u8 = 0xd;
+const UNSIGNED: u8 = 0xf;
+
+impl Usage for Display {
+    fn size(digits: usize) -> usize {
+        digits
+    }
+
+    fn encode(value: i128, digits: usize, _signed: bool) -> Vec<u8> {
+        let mut bytes = format!("{:0digits$}", value.unsigned_abs()).into_bytes();
+        if value < 0 {
+            let last = bytes.last_mut().unwrap();
+            *last = b'p' + (*last - b'0');
+        }
+        bytes
+    }
+
+    fn decode(bytes: &[u8], _signed: bool) -> i128 {
+        let mut negative = false;
+        let value = bytes.iter().fold(0, |value, byte| {
+            let digit = match byte {
+                b'p'..=b'y' => {
+                    negative = true;
+                    byte - b'p'
+                }
+                b'0'..=b'9' => byte - b'0',
+                _ => 0,
+            };
+            value * 10 + digit as i128
+        });
+        if negative {
+            -value
+        } else {
+            value
+        }
+    }
+
+    // digits, apart from the sign a signed field may have overpunched on the last of them
+    fn is_numeric(bytes: &[u8], signed: bool) -> bool {
+        let Some((last, digits)) = bytes.split_last() else {
+            return false;
+        };
+        digits.iter().all(u8::is_ascii_digit)
+            && (last.is_ascii_digit() || signed && matches!(last, b'p'..=b'y'))
+    }
+}
+
+impl Usage for LeadingSign {
+    fn size(digits: usize) -> usize {
+        digits
+    }
+
+    fn encode(value: i128, digits: usize, _signed: bool) -> Vec<u8> {
+        let mut bytes = format!("{:0digits$}", value.unsigned_abs()).into_bytes();
+        if value < 0 {
+            bytes[0] = b'p' + (bytes[0] - b'0');
+        }
+        bytes
+    }
+
+    fn decode(bytes: &[u8], signed: bool) -> i128 {
+        Display::decode(bytes, signed)
+    }
+
+    fn is_numeric(bytes: &[u8], signed: bool) -> bool {
+        let Some((first, digits)) = bytes.split_first() else {
+            return false;
+        };
+        digits.iter().all(u8::is_ascii_digit)
+            && (first.is_ascii_digit() || signed && matches!(first, b'p'..=b'y'))
+    }
+}
+
+impl Usage for LeadingSeparate {
+    fn size(digits: usize) -> usize {
+        digits + 1
+    }
+
+    fn encode(value: i128, digits: usize, _signed: bool) -> Vec<u8> {
+        format!("{}{:0digits$}", separate_sign(value), value.unsigned_abs()).into_bytes()
+    }
+
+    fn decode(bytes: &[u8], signed: bool) -> i128 {
+        let value = Display::decode(&bytes[1..], signed);
+        if bytes[0] == b'-' {
+            -value
+        } else {
+            value
+        }
+    }
+
+    fn is_numeric(bytes: &[u8], _signed: bool) -> bool {
+        let Some((sign, digits)) = bytes.split_first() else {
+            return false;
+        };
+        matches!(sign, b'+' | b'-') && digits.iter().all(u8::is_ascii_digit)
+    }
+}
+
+impl Usage for TrailingSeparate {
+    fn size(digits: usize) -> usize {
+        digits + 1
+    }
+
+    fn encode(value: i128, digits: usize, _signed: bool) -> Vec<u8> {
+        format!("{:0digits$}{}", value.unsigned_abs(), separate_sign(value)).into_bytes()
+    }
+
+    fn decode(bytes: &[u8], signed: bool) -> i128 {
+        let (sign, digits) = bytes.split_last().unwrap();
+        let value = Display::decode(digits, signed);
+        if *sign == b'-' {
+            -value
+        } else {
+            value
+        }
+    }
+
+    fn is_numeric(bytes: &[u8], _signed: bool) -> bool {
+        let Some((sign, digits)) = bytes.split_last() else {
+            return false;
+        };
+        matches!(sign, b'+' | b'-') && digits.iter().all(u8::is_ascii_digit)
+    }
+}
+
+fn separate_sign(value: i128) -> char {
+    if value < 0 {
+        '-'
+    } else {
+        '+'
+    }
+}
+
+impl Usage for Binary {
+    fn size(digits: usize) -> usize {
+        binary_size(digits)
+    }
+
+    fn encode(value: i128, digits: usize, _signed: bool) -> Vec<u8> {
+        let bytes = value.to_be_bytes();
+        bytes[bytes.len() - binary_size(digits)..].to_vec()
+    }
+
+    fn decode(bytes: &[u8], signed: bool) -> i128 {
+        let negative = signed && bytes.first().is_some_and(|b| b & 0x80 != 0);
+        let fill = if negative { 0xff } else { 0 };
+        let mut wide = [fill; 16];
+        wide[16 - bytes.len()..].copy_from_slice(bytes);
+        i128::from_be_bytes(wide)
+    }
+}
+
+impl Usage for PackedDecimal {
+    fn size(digits: usize) -> usize {
+        digits / 2 + 1
+    }
+
+    fn encode(value: i128, digits: usize, signed: bool) -> Vec<u8> {
+        let sign = match value {
+            _ if !signed => UNSIGNED,
+            ..0 => NEGATIVE,
+            _ => POSITIVE,
+        };
+        let width = Self::size(digits) * 2 - 1;
+        let nibbles: Vec<u8> = format!("{:0width$}", value.unsigned_abs())
+            .bytes()
+            .map(|b| b - b'0')
+            .chain([sign])
+            .collect();
+        nibbles
+            .chunks(2)
+            .map(|pair| pair[0] << 4 | pair[1])
+            .collect()
+    }
+
+    fn decode(bytes: &[u8], _signed: bool) -> i128 {
+        let digits = bytes
+            .iter()
+            .flat_map(|b| [b >> 4, b & 0xf])
+            .take(bytes.len() * 2 - 1)
+            .fold(0, |value, digit| value * 10 + digit as i128);
+        match bytes.last().map(|b| b & 0xf) {
+            Some(NEGATIVE | 0xb) => -digits,
+            _ => digits,
+        }
+    }
+
+    // decimal digits in every nibble but the last, which has to be a sign the field can have
+    fn is_numeric(bytes: &[u8], signed: bool) -> bool {
+        let nibbles: Vec<u8> = bytes.iter().flat_map(|b| [b >> 4, b & 0xf]).collect();
+        let Some((sign, digits)) = nibbles.split_last() else {
+            return false;
+        };
+        let valid_sign = if signed {
+            matches!(*sign, POSITIVE | NEGATIVE | UNSIGNED)
+        } else {
+            *sign == UNSIGNED
+        };
+        digits.iter().all(|d| *d <= 9) && valid_sign
+    }
+}
+
+impl Usage for NativeBinary {
+    fn size(digits: usize) -> usize {
+        binary_size(digits)
+    }
+
+    fn limit(digits: usize, signed: bool) -> i128 {
+        let bits = binary_size(digits) as u32 * 8 - signed as u32;
+        if bits >= 127 {
+            i128::MAX
+        } else {
+            1 << bits
+        }
+    }
+
+    fn encode(value: i128, digits: usize, _signed: bool) -> Vec<u8> {
+        let size = binary_size(digits);
+        let bytes = value.to_ne_bytes();
+        if cfg!(target_endian = "big") {
+            bytes[bytes.len() - size..].to_vec()
+        } else {
+            bytes[..size].to_vec()
+        }
+    }
+
+    fn decode(bytes: &[u8], signed: bool) -> i128 {
+        let mut big_endian = bytes.to_vec();
+        if cfg!(target_endian = "little") {
+            big_endian.reverse();
+        }
+        Binary::decode(&big_endian, signed)
+    }
+}
+
+// a halfword up to 4 digits, a fullword up to 9 and a doubleword up to 18
+fn binary_size(digits: usize) -> usize {
+    match digits {
+        ..=4 => 2,
+        5..=9 => 4,
+        10..=18 => 8,
+        _ => 16,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::usage::{
+        Binary, Display, LeadingSeparate, LeadingSign, NativeBinary, PackedDecimal,
+        TrailingSeparate, Usage,
+    };
+
+    fn round_trip<U: Usage>(value: i128, digits: usize, signed: bool) -> i128 {
+        let bytes = U::encode(value, digits, signed);
+        assert_eq!(bytes.len(), U::size(digits));
+        U::decode(&bytes, signed)
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Display::encode(-123, 4, true), b"012s");
+        for value in [0, 7, 1234, -1234, -10] {
+            assert_eq!(round_trip::<Display>(value, 4, true), value);
+        }
+    }
+
+    #[test]
+    fn test_sign() {
+        assert_eq!(LeadingSign::encode(-123, 4, true), b"p123");
+        assert_eq!(LeadingSeparate::encode(-123, 4, true), b"-0123");
+        assert_eq!(TrailingSeparate::encode(123, 4, true), b"0123+");
+        for value in [0, 7, 1234, -1234, -10] {
+            assert_eq!(round_trip::<LeadingSign>(value, 4, true), value);
+            assert_eq!(round_trip::<LeadingSeparate>(value, 4, true), value);
+            assert_eq!(round_trip::<TrailingSeparate>(value, 4, true), value);
+        }
+        assert!(!LeadingSign::is_numeric(b"p123", false));
+        assert!(!TrailingSeparate::is_numeric(b"0123 ", true));
+    }
+
+    #[test]
+    fn test_packed_decimal() {
+        assert_eq!(PackedDecimal::encode(-123, 5, true), [0x00, 0x12, 0x3d]);
+        assert_eq!(PackedDecimal::encode(123, 5, true), [0x00, 0x12, 0x3c]);
+        assert_eq!(PackedDecimal::encode(123, 4, false), [0x00, 0x12, 0x3f]);
+        for value in [0, 1, 99999, -99999, -5] {
+            assert_eq!(round_trip::<PackedDecimal>(value, 5, true), value);
+        }
+        assert_eq!(round_trip::<PackedDecimal>(1234, 4, false), 1234);
+    }
+
+    #[test]
+    fn test_binary() {
+        assert_eq!(Binary::encode(-2, 4, true), [0xff, 0xfe]);
+        assert_eq!(Binary::encode(300, 9, false), [0x00, 0x00, 0x01, 0x2c]);
+        for (value, digits) in [
+            (0, 4),
+            (9999, 4),
+            (-9999, 4),
+            (-1, 9),
+            (999_999_999_999, 18),
+        ] {
+            assert_eq!(round_trip::<Binary>(value, digits, true), value);
+        }
+        assert_eq!(round_trip::<Binary>(65535, 4, false), 65535);
+    }
+
+    #[test]
+    fn test_native_binary() {
+        assert_eq!(NativeBinary::encode(1, 4, true), 1i16.to_ne_bytes());
+        for (value, digits) in [
+            (0, 4),
+            (32767, 4),
+            (-32768, 4),
+            (-1, 9),
+            (i64::MIN as i128, 18),
+        ] {
+            assert_eq!(round_trip::<NativeBinary>(value, digits, true), value);
+        }
+        assert_eq!(round_trip::<NativeBinary>(65535, 4, false), 65535);
+        assert_eq!(NativeBinary::limit(4, true), 32768);
+        assert_eq!(NativeBinary::limit(4, false), 65536);
+    }
+
+    #[test]
+    fn test_is_numeric() {
+        assert!(Display::is_numeric(b"012s", true));
+        assert!(!Display::is_numeric(b"012s", false));
+        assert!(!Display::is_numeric(b"01 3", true));
+        assert!(PackedDecimal::is_numeric(&[0x12, 0x3d], true));
+        assert!(!PackedDecimal::is_numeric(&[0x12, 0x3d], false));
+        assert!(!PackedDecimal::is_numeric(&[0x1a, 0x3c], true));
+    }
+}
@@ -0,0 +1,350 @@
+use crate::parser::{
+    Arithmetic, Condition, Instruction, Relation, Section, SizeError, Value, When,
+};
+
+use std::sync::Arc;
+
+// the procedure division once every go to has been replaced by something rust can express. when
+// all of them could be turned into structured control flow the paragraphs stay plain methods,
+// otherwise dispatch is set and the paragraphs are run through a state machine instead, where
+// each of them returns the index of the paragraph to continue with
+pub struct Flow {
+    pub sections: Vec<Section>,
+    pub dispatch: bool,
+}
+
+// the name of a paragraph and of the section it is in
+type Unit = (Option<Arc<str>>, Option<Arc<str>>);
+
+// every paragraph in the order they appear, which is also the order control falls through them.
+// returns marks the paragraphs that a perform of a single procedure returns at the end of, which
+// is the paragraph itself or the last one of a section
+struct Units {
+    names: Vec<Unit>,
+    returns: Vec<bool>,
+}
+
+impl Units {
+    fn new(sections: &[Section]) -> Self {
+        let names: Vec<Unit> = sections
+            .iter()
+            .flat_map(|s| {
+                s.paragraphs
+                    .iter()
+                    .map(|p| (s.name.clone(), p.name.clone()))
+            })
+            .collect();
+
+        let mut units = Self {
+            returns: vec![false; names.len()],
+            names,
+        };
+        let paragraphs = sections.iter().flat_map(|s| &s.paragraphs);
+        for (current, paragraph) in paragraphs.enumerate() {
+            units.find_returns(&paragraph.insts, current);
+        }
+        units
+    }
+
+    fn find_returns(&mut self, insts: &[Instruction], current: usize) {
+        for inst in insts {
+            match inst {
+                Instruction::Perform { from, thru } if thru.as_ref().is_none_or(|t| t == from) => {
+                    let end = self.end_of(from, current);
+                    self.returns[end] = true;
+                }
+                Instruction::Repeat { insts, .. }
+                | Instruction::Varying { insts, .. }
+                | Instruction::Times { insts, .. }
+                | Instruction::Inline(insts)
+                | Instruction::Sentence(insts) => self.find_returns(insts, current),
+                Instruction::If {
+                    then, otherwise, ..
+                } => {
+                    self.find_returns(then, current);
+                    self.find_returns(otherwise, current);
+                }
+                Instruction::Evaluate { whens, other, .. } => {
+                    for when in whens {
+                        self.find_returns(&when.insts, current);
+                    }
+                    self.find_returns(other, current);
+                }
+                Instruction::Add(Arithmetic { size_error, .. })
+                | Instruction::Subtract(Arithmetic { size_error, .. })
+                | Instruction::Multiply(Arithmetic { size_error, .. })
+                | Instruction::Divide {
+                    arithmetic: Arithmetic { size_error, .. },
+                    ..
+                }
+                | Instruction::Compute { size_error, .. } => {
+                    for handler in size_error.handlers() {
+                        self.find_returns(handler, current);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // the last paragraph of a procedure, which is where a perform of it returns
+    fn end_of(&self, target: &str, current: usize) -> usize {
+        if target.contains(" of ") {
+            return self.resolve(target, current);
+        }
+        self.names
+            .iter()
+            .rposition(|(s, _)| s.as_deref() == Some(target))
+            .unwrap_or_else(|| self.resolve(target, current))
+    }
+
+    // a section is entered through its first paragraph. a paragraph can be qualified by its
+    // section, and has to be when its name is used in more than one section, unless the reference
+    // is from inside of one of them
+    fn resolve(&self, target: &str, current: usize) -> usize {
+        if let Some((paragraph, section)) = target.split_once(" of ") {
+            return self
+                .names
+                .iter()
+                .position(|(s, p)| s.as_deref() == Some(section) && p.as_deref() == Some(paragraph))
+                .unwrap_or_else(|| panic!("unknown procedure {target}"));
+        }
+
+        if let Some(section) = self
+            .names
+            .iter()
+            .position(|(s, _)| s.as_deref() == Some(target))
+        {
+            return section;
+        }
+
+        let matches: Vec<usize> = (0..self.names.len())
+            .filter(|&i| self.names[i].1.as_deref() == Some(target))
+            .collect();
+        match matches[..] {
+            [] => panic!("unknown procedure {target}"),
+            [paragraph] => paragraph,
+            _ => *matches
+                .iter()
+                .find(|&&i| self.names[i].0 == self.names[current].0)
+                .unwrap_or_else(|| {
+                    panic!("procedure {target} needs to be qualified by its section")
+                }),
+        }
+    }
+
+    // the name translate looks a procedure up by, which is the section name for a section and the
+    // paragraph name qualified by its section (if it has one) for a paragraph
+    fn qualify(&self, target: Arc<str>, current: usize) -> Arc<str> {
+        if !target.contains(" of ") && self.names.iter().any(|(s, _)| s.as_ref() == Some(&target)) {
+            return target;
+        }
+
+        match &self.names[self.resolve(&target, current)] {
+            (Some(section), Some(paragraph)) => format!("{paragraph} of {section}").into(),
+            (_, paragraph) => paragraph.clone().unwrap(),
+        }
+    }
+}
+
+struct Rewriter<'a> {
+    units: &'a Units,
+    current: usize,
+    section_end: usize,
+    restarts: bool,
+    dispatch: bool,
+}
+
+impl Rewriter<'_> {
+    fn rewrite(&mut self, instructions: Vec<Instruction>) -> Vec<Instruction> {
+        instructions
+            .into_iter()
+            .map(|inst| self.rewrite_instruction(inst))
+            .collect()
+    }
+
+    fn rewrite_instruction(&mut self, inst: Instruction) -> Instruction {
+        match inst {
+            Instruction::GoTo {
+                targets,
+                depending: None,
+            } => {
+                let target = self.units.resolve(&targets[0], self.current);
+                if target == self.current {
+                    self.restarts = true;
+                    Instruction::RestartParagraph
+                } else if target == self.current + 1 && !self.units.returns[self.current] {
+                    Instruction::ExitParagraph
+                } else {
+                    self.dispatch = true;
+                    Instruction::Jump(target)
+                }
+            }
+            Instruction::GoTo {
+                targets,
+                depending: Some(value),
+            } => {
+                let branches = depending_on(targets, value);
+                self.rewrite_instruction(branches)
+            }
+            Instruction::Perform { from, thru } => Instruction::Perform {
+                from: self.units.qualify(from, self.current),
+                thru: thru.map(|thru| self.units.qualify(thru, self.current)),
+            },
+            Instruction::ExitSection if self.current == self.section_end => {
+                Instruction::ExitParagraph
+            }
+            Instruction::ExitSection => {
+                self.dispatch = true;
+                Instruction::Leave(self.section_end)
+            }
+            Instruction::Repeat {
+                condition,
+                test_after,
+                insts,
+            } => Instruction::Repeat {
+                condition,
+                test_after,
+                insts: self.rewrite(insts),
+            },
+            Instruction::Varying {
+                varyings,
+                test_after,
+                insts,
+            } => Instruction::Varying {
+                varyings,
+                test_after,
+                insts: self.rewrite(insts),
+            },
+            Instruction::Times { count, insts } => Instruction::Times {
+                count,
+                insts: self.rewrite(insts),
+            },
+            Instruction::Inline(insts) => Instruction::Inline(self.rewrite(insts)),
+            Instruction::If {
+                condition,
+                then,
+                otherwise,
+            } => Instruction::If {
+                condition,
+                then: self.rewrite(then),
+                otherwise: self.rewrite(otherwise),
+            },
+            Instruction::Evaluate {
+                subjects,
+                whens,
+                other,
+            } => Instruction::Evaluate {
+                subjects,
+                whens: whens
+                    .into_iter()
+                    .map(|when| When {
+                        selections: when.selections,
+                        insts: self.rewrite(when.insts),
+                    })
+                    .collect(),
+                other: self.rewrite(other),
+            },
+            Instruction::Sentence(insts) => Instruction::Sentence(self.rewrite(insts)),
+            Instruction::Add(arithmetic) => Instruction::Add(self.rewrite_arithmetic(arithmetic)),
+            Instruction::Subtract(arithmetic) => {
+                Instruction::Subtract(self.rewrite_arithmetic(arithmetic))
+            }
+            Instruction::Multiply(arithmetic) => {
+                Instruction::Multiply(self.rewrite_arithmetic(arithmetic))
+            }
+            Instruction::Divide {
+                arithmetic,
+                remainder,
+            } => Instruction::Divide {
+                arithmetic: self.rewrite_arithmetic(arithmetic),
+                remainder,
+            },
+            Instruction::Compute {
+                receivers,
+                expression,
+                size_error,
+            } => Instruction::Compute {
+                receivers,
+                expression,
+                size_error: self.rewrite_size_error(size_error),
+            },
+            inst => inst,
+        }
+    }
+
+    fn rewrite_arithmetic(&mut self, arithmetic: Arithmetic) -> Arithmetic {
+        Arithmetic {
+            size_error: self.rewrite_size_error(arithmetic.size_error),
+            ..arithmetic
+        }
+    }
+
+    // the statements of the size error phrases can go to procedures like any others
+    fn rewrite_size_error(&mut self, size_error: SizeError) -> SizeError {
+        SizeError {
+            on: size_error.on.map(|insts| self.rewrite(insts)),
+            not_on: size_error.not_on.map(|insts| self.rewrite(insts)),
+        }
+    }
+}
+
+// go to a b c depending on x goes to the x-th procedure, and does nothing when x is out of range
+fn depending_on(targets: Vec<Arc<str>>, value: Value) -> Instruction {
+    let mut otherwise = vec![];
+    for (i, target) in targets.into_iter().enumerate().rev() {
+        let branch = Instruction::If {
+            condition: Condition::Relation {
+                left: value.clone(),
+                relation: Relation::EqualTo,
+                right: Value::Number(i as i32 + 1),
+            },
+            then: vec![Instruction::GoTo {
+                targets: vec![target],
+                depending: None,
+            }],
+            otherwise,
+        };
+        otherwise = vec![branch];
+    }
+
+    otherwise.pop().unwrap()
+}
+
+// a go to the paragraph it is in starts that paragraph over, and one to the paragraph right after
+// it leaves the paragraph early, both of which a loop and a return can do. the return only works
+// when no perform of the paragraph on its own would take it back to the perform, since the go to
+// carries on into the next paragraph instead. anything else, including an exit section that skips
+// over paragraphs, needs the dispatch loop
+pub fn recover(mut sections: Vec<Section>) -> Flow {
+    let units = Units::new(&sections);
+    let mut dispatch = false;
+    let mut current = 0;
+
+    for section in &mut sections {
+        if section.paragraphs.is_empty() {
+            continue;
+        }
+        let section_end = current + section.paragraphs.len() - 1;
+        for paragraph in &mut section.paragraphs {
+            let mut rewriter = Rewriter {
+                units: &units,
+                current,
+                section_end,
+                restarts: false,
+                dispatch: false,
+            };
+            let insts = rewriter.rewrite(std::mem::take(&mut paragraph.insts));
+            paragraph.insts = if rewriter.restarts {
+                vec![Instruction::ParagraphLoop(insts)]
+            } else {
+                insts
+            };
+
+            dispatch |= rewriter.dispatch;
+            current += 1;
+        }
+    }
+
+    Flow { sections, dispatch }
+}
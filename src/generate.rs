@@ -1,3 +1,4 @@
+use std::env;
 use std::fs;
 use std::path::Path;
 use std::process::Command;
@@ -7,9 +8,12 @@ pub fn generate(output: String, file_name: String) -> std::io::Result<()> {
         fs::create_dir_all("out/src")?;
     }
 
-    let name = file_name.split(".").into_iter().next().unwrap();
+    let name = file_name.split(".").next().unwrap();
 
-    #[cfg(dev)]
+    // the runtime is part of this workspace, so the generated program depends on it by path. out is
+    // created in the directory cello is run from, which is next to the runtime when that is the
+    // workspace root. CELLO_RUNTIME points the program at the runtime from anywhere else
+    let runtime = env::var("CELLO_RUNTIME").unwrap_or_else(|_| String::from("../runtime/"));
     let cargo_toml = format!(
         "[package]
 name = \"{}\"
@@ -17,21 +21,9 @@ version = \"0.1.0\"
 edition = \"2021\"
 
 [dependencies]
-conum = {{ path = \"../../conum/\" }}
+cello-runtime = {{ path = \"{}\" }}
     ",
-        name
-    );
-    #[cfg(not(dev))]
-    let cargo_toml = format!(
-        "[package]
-name = \"{}\"
-version = \"0.1.0\"
-edition = \"2021\"
-
-[dependencies]
-conum = \"*\"
-    ",
-        name
+        name, runtime
     );
 
     fs::write("out/src/main.rs", output)?;
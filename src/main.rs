@@ -1,3 +1,4 @@
+mod flow;
 mod generate;
 mod lexer;
 mod parser;
@@ -21,8 +22,9 @@ fn main() {
                          // result in annoying lifetime errors
 
     let parser = parser::Parser::new(&file_string);
-    let (data, ast) = parser.parse();
-    let file = translate::translate(data, ast);
+    let (data, sections) = parser.parse();
+    let flow = flow::recover(sections);
+    let file = translate::translate(data, flow);
 
     if let Err(e) = generate::generate(file, file_name) {
         panic!("{}", e);
@@ -1,15 +1,17 @@
 #![allow(dead_code)]
-use crate::lexer::{self, Tail};
+use crate::lexer;
 use std::{
     collections::VecDeque,
     fmt::{Display, Formatter},
+    iter::Peekable,
     sync::Arc,
 };
 
+// an item without a picture is a group, whose storage is that of its children one after another
 #[derive(Debug, Clone)]
 pub enum DataType {
     Picture(IdentifierType),
-    Other,
+    Group,
 }
 
 #[derive(Debug)]
@@ -17,341 +19,3346 @@ pub struct Data {
     pub level: i32,
     pub name: Arc<str>,
     pub data_type: DataType,
+    pub usage: Usage,
+    pub value: Option<Value>,
+    // justified right, which fills an alphanumeric item from the right when it is moved into
+    pub justified: bool,
+    pub occurs: Option<Occurs>,
+    pub redefines: Option<Arc<str>>,
+    pub renames: Option<Renames>,
+    pub conditions: Vec<ConditionName>,
+    pub children: Vec<Data>,
+}
+
+impl Data {
+    pub fn is_filler(&self) -> bool {
+        &*self.name == "filler"
+    }
+
+    // an item that redefines or renames others has no storage of its own, it is another way of
+    // looking at theirs
+    pub fn is_view(&self) -> bool {
+        self.redefines.is_some() || self.renames.is_some()
+    }
+
+    // how many bytes the item takes, with every entry when it is a table
+    pub fn size(&self) -> u32 {
+        let entry = match &self.data_type {
+            DataType::Picture(IdentifierType::Numeric(picture)) => self.usage.size(picture.digits),
+            DataType::Picture(
+                IdentifierType::Alphabetic(length) | IdentifierType::Alphanumeric(length),
+            ) => *length,
+            DataType::Picture(
+                IdentifierType::NumericEdited(picture)
+                | IdentifierType::AlphanumericEdited(picture),
+            ) => picture.length,
+            DataType::Picture(kind) => unreachable!("{kind:?} has no size"),
+            DataType::Group => match &self.renames {
+                Some(renames) => renames.length,
+                None => self
+                    .children
+                    .iter()
+                    .filter(|c| !c.is_view())
+                    .map(Data::size)
+                    .sum(),
+            },
+        };
+        entry * self.occurs.as_ref().map_or(1, |occurs| occurs.max)
+    }
+
+    pub fn kind(&self) -> IdentifierType {
+        match &self.data_type {
+            DataType::Picture(kind) => kind.clone(),
+            DataType::Group => IdentifierType::Group,
+        }
+    }
+
+    // the table with occurs depending on that a group ends with, if it does. the group only takes
+    // up as many bytes as the entries of it that are in use
+    pub fn depending_table(&self) -> Option<&Data> {
+        let last = self.children.iter().rfind(|c| !c.is_view())?;
+        match &last.occurs {
+            Some(Occurs {
+                depending: Some(_), ..
+            }) => Some(last),
+            _ => last.depending_table(),
+        }
+    }
+}
+
+// an item that occurs more than once is a table of max entries. with depending on, only the first
+// as many as the depending item holds are in use, which is what subscripts are checked against,
+// but storage is always set aside for all of them
+#[derive(Debug, Clone)]
+pub struct Occurs {
+    pub min: u32,
+    pub max: u32,
+    pub depending: Option<Arc<str>>,
+    pub keys: Vec<Key>,
+    pub indexes: Vec<Arc<str>>,
+}
+
+// a level 66 entry, which is a new name for length bytes of the record before it, from offset on.
+// it renames either a single item, whose picture it takes, or the items from one through another
+// as if they were a group
+#[derive(Debug, Clone)]
+pub struct Renames {
+    pub offset: u32,
+    pub length: u32,
+}
+
+// a key the entries of a table are ordered by, for search all
+#[derive(Debug, Clone)]
+pub struct Key {
+    pub name: Arc<str>,
+    pub ascending: bool,
+}
+
+// how a field is stored. display keeps one character per digit, binary a big-endian two's
+// complement integer, packed decimal two digits per byte with the sign in the last nibble, and
+// native binary an integer in the byte order of the machine that is not limited to its picture.
+// the sign clause moves the sign of a display item to its first digit, or into a character of its
+// own before or after the digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Usage {
+    Display,
+    Binary,
+    PackedDecimal,
+    NativeBinary,
+    LeadingSign,
+    LeadingSeparate,
+    TrailingSeparate,
+}
+
+impl Usage {
+    // how many bytes a numeric field of that many digits takes, which is what the runtime lays it
+    // out in. binary fields take a halfword up to 4 digits, a fullword up to 9 and a doubleword up
+    // to 18
+    pub fn size(self, digits: u32) -> u32 {
+        match self {
+            Usage::Display | Usage::LeadingSign => digits,
+            Usage::LeadingSeparate | Usage::TrailingSeparate => digits + 1,
+            Usage::PackedDecimal => digits / 2 + 1,
+            Usage::Binary | Usage::NativeBinary => match digits {
+                ..=4 => 2,
+                5..=9 => 4,
+                10..=18 => 8,
+                _ => 16,
+            },
+        }
+    }
+
+    fn parse(word: &str) -> Option<Self> {
+        match word {
+            "display" => Some(Usage::Display),
+            "comp" | "computational" | "comp-4" | "computational-4" | "binary" => {
+                Some(Usage::Binary)
+            }
+            "comp-3" | "computational-3" | "packed-decimal" => Some(Usage::PackedDecimal),
+            "comp-5" | "computational-5" => Some(Usage::NativeBinary),
+            _ => None,
+        }
+    }
+
+    fn is_sign(self) -> bool {
+        matches!(
+            self,
+            Usage::LeadingSign | Usage::LeadingSeparate | Usage::TrailingSeparate
+        )
+    }
+}
+
+// a level 88 entry, holding the values of its parent item that make the condition true
+#[derive(Debug)]
+pub struct ConditionName {
+    pub name: Arc<str>,
+    pub values: Vec<ConditionValue>,
+}
+
+#[derive(Debug)]
+pub enum ConditionValue {
+    Single(Value),
+    Range(Value, Value),
 }
 
 trait Derive {
     fn derive(val: &str) -> Self;
 }
 
-#[derive(Debug)]
-pub enum Condition {
-    GreaterThan,
-    LessThan,
-    EqualTo,
+#[derive(Debug, Clone, Copy)]
+pub enum Relation {
+    GreaterThan,
+    LessThan,
+    EqualTo,
+    GreaterOrEqual,
+    LessOrEqual,
+    NotEqual,
+}
+
+impl Relation {
+    fn negate(self) -> Self {
+        match self {
+            Relation::GreaterThan => Relation::LessOrEqual,
+            Relation::LessThan => Relation::GreaterOrEqual,
+            Relation::EqualTo => Relation::NotEqual,
+            Relation::GreaterOrEqual => Relation::LessThan,
+            Relation::LessOrEqual => Relation::GreaterThan,
+            Relation::NotEqual => Relation::EqualTo,
+        }
+    }
+}
+
+impl Display for Relation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Relation::EqualTo => write!(f, "=="),
+            Relation::GreaterThan => write!(f, ">"),
+            Relation::LessThan => write!(f, "<"),
+            Relation::GreaterOrEqual => write!(f, ">="),
+            Relation::LessOrEqual => write!(f, "<="),
+            Relation::NotEqual => write!(f, "!="),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Condition {
+    Relation {
+        left: Value,
+        relation: Relation,
+        right: Value,
+    },
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+    Named {
+        name: Arc<str>,
+        parent: Ident,
+    },
+    Class {
+        value: Value,
+        class: Class,
+    },
+    Sign {
+        value: Value,
+        sign: Sign,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Class {
+    Numeric,
+    Alphabetic,
+    AlphabeticLower,
+    AlphabeticUpper,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Sign {
+    Positive,
+    Negative,
+    Zero,
+}
+
+// the shape of a numeric field. digits counts the 9s and scale how many places after the decimal
+// point the last of them is. a p is a digit position that is not stored, so leading ones make the
+// scale larger than the digits (ppp999 holds .000nnn) and trailing ones make it negative (999pp
+// holds nnn00)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumericPicture {
+    pub digits: u32,
+    pub scale: i32,
+    pub signed: bool,
+}
+
+impl NumericPicture {
+    pub fn integer(digits: u32) -> Self {
+        Self {
+            digits,
+            scale: 0,
+            signed: false,
+        }
+    }
+
+    // the symbols are already lowercase and expanded. without a v the decimal point is to the
+    // left of leading ps and to the right of everything else
+    fn parse(symbols: &[char]) -> Option<Self> {
+        let signed = symbols.first() == Some(&'s');
+        let body = if signed { &symbols[1..] } else { symbols };
+        if body.is_empty() || !body.iter().all(|c| matches!(c, '9' | 'v' | 'p')) {
+            return None;
+        }
+        if body.iter().filter(|c| **c == 'v').count() > 1 {
+            panic!("picture {} has more than one v", String::from_iter(symbols));
+        }
+
+        let point = match body.iter().position(|c| *c == 'v') {
+            Some(point) => point,
+            None if body[0] == 'p' => 0,
+            None => body.len(),
+        };
+        let (before, after) = body.split_at(point);
+        let scaling = before.iter().filter(|c| **c == 'p').count() as i32;
+        let places = after.iter().filter(|c| **c != 'v').count() as i32;
+
+        Some(Self {
+            digits: body.iter().filter(|c| **c == '9').count() as u32,
+            scale: places - scaling,
+            signed,
+        })
+    }
+}
+
+// a picture with editing symbols in it. the field holds the edited text, so only the expanded
+// symbols are kept, to be applied by the runtime whenever something is moved into it
+#[derive(Debug, Clone)]
+pub struct EditedPicture {
+    pub symbols: Arc<str>,
+    pub length: u32,
+}
+
+impl EditedPicture {
+    // numeric editing replaces and inserts characters around the digits of a number: z and * for
+    // zero suppression, a floating $, + or -, fixed signs and cr or db, and the insertion
+    // characters , . b 0 and /
+    fn numeric(symbols: &[char]) -> Option<Self> {
+        let mut chars = symbols.iter().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '9' | 'z' | '*' | '$' | '+' | '-' | ',' | '.' | 'v' | 'b' | '0' | '/' => {}
+                'c' if chars.next_if_eq(&&'r').is_some() => {}
+                'd' if chars.next_if_eq(&&'b').is_some() => {}
+                _ => return None,
+            }
+        }
+
+        Some(Self::new(symbols))
+    }
+
+    // alphanumeric editing only inserts b, 0 and / between the characters of the value
+    fn alphanumeric(symbols: &[char]) -> Option<Self> {
+        let valid = symbols
+            .iter()
+            .all(|c| matches!(c, 'x' | 'a' | '9' | 'b' | '0' | '/'));
+        let has_characters = symbols.iter().any(|c| matches!(c, 'x' | 'a'));
+
+        (valid && has_characters).then(|| Self::new(symbols))
+    }
+
+    fn new(symbols: &[char]) -> Self {
+        Self {
+            symbols: Arc::from(String::from_iter(symbols)),
+            length: symbols.iter().filter(|c| **c != 'v').count() as u32,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum IdentifierType {
+    Numeric(NumericPicture),
+    NumericEdited(EditedPicture),
+    Alphabetic(u32),
+    Alphanumeric(u32),
+    AlphanumericEdited(EditedPicture),
+    Group,
+}
+
+impl IdentifierType {
+    fn parse_type(string: &str) -> Option<Self> {
+        let symbols = expand_picture(string);
+        if let Some(picture) = NumericPicture::parse(&symbols) {
+            Some(IdentifierType::Numeric(picture))
+        } else if symbols.iter().all(|c| *c == 'a') {
+            Some(IdentifierType::Alphabetic(symbols.len() as u32))
+        } else if symbols.iter().all(|c| matches!(c, 'x' | '9' | 'a')) {
+            Some(IdentifierType::Alphanumeric(symbols.len() as u32))
+        } else if let Some(picture) = EditedPicture::alphanumeric(&symbols) {
+            Some(IdentifierType::AlphanumericEdited(picture))
+        } else {
+            EditedPicture::numeric(&symbols).map(IdentifierType::NumericEdited)
+        }
+    }
+}
+
+// a symbol followed by a count in parentheses stands for that many of it, so s9(3)v9(2) is the
+// same picture as s999v99
+fn expand_picture(picture: &str) -> Vec<char> {
+    let mut symbols = vec![];
+    let mut chars = picture.chars();
+    while let Some(c) = chars.next() {
+        if c != '(' {
+            symbols.push(c.to_ascii_lowercase());
+            continue;
+        }
+
+        let count: String = chars.by_ref().take_while(|c| *c != ')').collect();
+        let count: usize = count
+            .parse()
+            .expect("defined var has () without a number inside");
+        let symbol = symbols.pop().expect("picture starts with (");
+        symbols.extend(std::iter::repeat_n(symbol, count));
+    }
+
+    symbols
+}
+
+// path holds the names of the groups the item is in, outermost first, and subscripts which entry
+// of every table among them (or of the item itself) is meant. views are the positions of those
+// (or of the item) that redefine or rename others, where the name of the item counts as coming
+// after the last group. a variable item is a group that ends with a table with occurs depending on
+#[derive(Debug, Clone)]
+pub struct Ident {
+    pub name: Arc<str>,
+    pub kind: IdentifierType,
+    pub path: Vec<Arc<str>>,
+    pub subscripts: Vec<Subscript>,
+    pub views: Vec<usize>,
+    pub justified: bool,
+    pub variable: bool,
+}
+
+// a subscript of the table at position in the path of an ident, where the name of the ident itself
+// comes after the last group
+#[derive(Debug, Clone)]
+pub struct Subscript {
+    pub position: usize,
+    pub index: Index,
+    pub count: Count,
+}
+
+// which entry a subscript picks. offset is what a relative subscript (idx + 1) adds to the value.
+// every entry in turn is what a statement that goes through a whole table, like initialize, uses,
+// and stands for the loop variable it generates for the table
+#[derive(Debug, Clone)]
+pub enum Index {
+    Value { value: Value, offset: i32 },
+    Every,
+}
+
+// how many entries of a table are in use, which a subscript has to be within
+#[derive(Debug, Clone)]
+pub enum Count {
+    Fixed(u32),
+    Depending(Box<Ident>),
+}
+
+// cobol names can contain hyphens, rust ones cannot. a name that happens to be a rust keyword is
+// written as a raw identifier
+pub fn rust_name(name: &str) -> String {
+    let name = name.replace('-', "_");
+    match name.as_str() {
+        "as" | "break" | "const" | "continue" | "crate" | "else" | "enum" | "extern" | "false"
+        | "fn" | "for" | "if" | "impl" | "in" | "let" | "loop" | "match" | "mod" | "move"
+        | "mut" | "pub" | "ref" | "return" | "static" | "struct" | "trait" | "true" | "type"
+        | "unsafe" | "use" | "where" | "while" | "async" | "await" | "dyn" | "abstract"
+        | "become" | "box" | "do" | "final" | "macro" | "override" | "priv" | "typeof"
+        | "unsized" | "virtual" | "yield" | "try" | "gen" => format!("r#{}", name),
+        _ => name,
+    }
+}
+
+// every identifier is a field of the generated program struct, which holds the working storage. a
+// table is an array, indexed by the runtime once it has checked the subscript, and an item without
+// storage of its own is reached through an accessor of the group (or program) it is in
+impl Display for Ident {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", self.place(""))
+    }
+}
+
+impl Ident {
+    pub fn new(name: &str, kind: IdentifierType) -> Self {
+        Self {
+            name: Arc::from(name),
+            kind,
+            path: vec![],
+            subscripts: vec![],
+            views: vec![],
+            justified: false,
+            variable: false,
+        }
+    }
+
+    // the item as something that is stored into, which an accessor has to hand out mutably
+    pub fn mutable(&self) -> String {
+        self.place("_mut")
+    }
+
+    // the bytes of the item that are in use, which for a variable item end after the last entry
+    // of its table that is
+    pub fn bytes(&self) -> String {
+        if self.variable {
+            format!(
+                "{}.bytes_to(self.{}())",
+                self,
+                length_accessor(&self.path, &self.name)
+            )
+        } else {
+            format!("{}.bytes()", self)
+        }
+    }
+
+    pub fn alphanumeric(&self) -> String {
+        if self.variable {
+            format!("Alphanumeric::from({})", self.bytes())
+        } else {
+            format!("{}.alphanumeric()", self)
+        }
+    }
+
+    fn place(&self, accessor: &str) -> String {
+        let mut place = String::from("self");
+        for (position, name) in self.path.iter().chain([&self.name]).enumerate() {
+            place += &format!(".{}", rust_name(name));
+            if self.views.contains(&position) {
+                place += &format!("{}()", accessor);
+            }
+            if let Some(subscript) = self.subscripts.iter().find(|s| s.position == position) {
+                place += &format!("[{}]", subscript);
+            }
+        }
+
+        place
+    }
+
+    // whether one of the two is inside of the other, or they are the same item. an item seen
+    // through a view shares its bytes with items of other names, so all that can be told for it is
+    // whether the two are in the same group as the view
+    pub fn overlaps(&self, other: &Ident) -> bool {
+        fn storage(ident: &Ident) -> impl Iterator<Item = &Arc<str>> {
+            let names = ident.path.iter().chain([&ident.name]);
+            names.take(ident.views.iter().min().map_or(usize::MAX, |view| *view))
+        }
+        storage(self).zip(storage(other)).all(|(l, r)| l == r)
+    }
+}
+
+impl Subscript {
+    // the loop variable that goes through every entry of the table, counting from zero
+    pub fn entry(&self) -> String {
+        format!("entry_{}", self.position)
+    }
+}
+
+impl Display for Subscript {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        let (value, offset) = match &self.index {
+            Index::Value { value, offset } => (value, offset),
+            Index::Every => return write!(f, "{}", self.entry()),
+        };
+        match value {
+            Value::Identifier(ident) => write!(f, "subscript({}.decimal()", ident)?,
+            value => write!(f, "subscript(Decimal::from({})", value)?,
+        }
+        if *offset != 0 {
+            write!(f, " + Decimal::from({})", offset)?;
+        }
+        match &self.count {
+            Count::Fixed(max) => write!(f, ", Decimal::from({}))", max),
+            Count::Depending(ident) => write!(f, ", {}.decimal())", ident),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Value {
+    Number(i32),
+    // a literal with a decimal point, or one too large for a number
+    Decimal { value: i128, scale: u32 },
+    Identifier(Ident),
+    String(Arc<str>),
+    Figurative(Figurative),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            Value::Number(i) => write!(f, "{}", i),
+            Value::Decimal { value, scale } => write!(f, "{}", format_decimal(*value, *scale)),
+            Value::Identifier(ident) => write!(f, "{}", ident),
+            Value::String(s) => write!(f, "{}", byte_string(s.as_bytes())),
+            Value::Figurative(figurative) => write!(f, "{}", byte_string(&figurative.bytes())),
+        }
+    }
+}
+
+// a figurative constant stands for as many of its characters as the item it is used with has room
+// for. all literal does the same with the characters of the literal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Figurative {
+    Zero,
+    Space,
+    HighValue,
+    LowValue,
+    Quote,
+    All(Arc<str>),
+}
+
+impl Figurative {
+    fn parse(word: &str) -> Option<Self> {
+        let figurative = match word {
+            "zero" | "zeros" | "zeroes" => Figurative::Zero,
+            "space" | "spaces" => Figurative::Space,
+            "high-value" | "high-values" => Figurative::HighValue,
+            "low-value" | "low-values" => Figurative::LowValue,
+            "quote" | "quotes" => Figurative::Quote,
+            _ => {
+                let literal = word.strip_prefix("all ")?;
+                return match literal.strip_prefix('"').and_then(|l| l.strip_suffix('"')) {
+                    Some("") => panic!("all needs a literal with at least one character"),
+                    Some(literal) => Some(Figurative::All(Arc::from(literal))),
+                    None => Figurative::parse(literal),
+                };
+            }
+        };
+        Some(figurative)
+    }
+
+    // the bytes that are repeated, which for the figurative constants is a single one
+    pub fn bytes(&self) -> Vec<u8> {
+        match self {
+            Figurative::Zero => vec![b'0'],
+            Figurative::Space => vec![b' '],
+            Figurative::HighValue => vec![0xff],
+            Figurative::LowValue => vec![0x00],
+            Figurative::Quote => vec![b'"'],
+            Figurative::All(literal) => literal.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl Derive for Value {
+    fn derive(val: &str) -> Self {
+        let try_parse = val.parse::<i32>();
+        if let Ok(value) = try_parse {
+            return Self::Number(value);
+        }
+
+        if val.starts_with('\"') && val.ends_with('\"') {
+            let actual_string = val.strip_prefix('\"').unwrap().strip_suffix('\"').unwrap();
+            return Self::String(Arc::from(actual_string));
+        }
+
+        if let Some((value, scale)) = parse_numeric_literal(val) {
+            return Self::Decimal { value, scale };
+        }
+
+        if let Some(figurative) = Figurative::parse(val) {
+            return Self::Figurative(figurative);
+        }
+
+        Value::Identifier(Ident::new(
+            val,
+            IdentifierType::Numeric(NumericPicture::integer(0)),
+        ))
+    }
+}
+
+#[derive(Debug)]
+pub struct Infix {
+    pub left: Value,
+    pub right: Ident,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+}
+
+// an arithmetic expression, as in compute. numeric literals are kept as their digits and the
+// number of those after the decimal point
+#[derive(Debug, Clone)]
+pub enum Expression {
+    Value(Value),
+    Literal {
+        value: i128,
+        scale: u32,
+    },
+    Negate(Box<Expression>),
+    Binary {
+        left: Box<Expression>,
+        operator: Operator,
+        right: Box<Expression>,
+    },
+}
+
+// the rounding modes of the 2014 standard, a plain rounded rounds to the nearest value and away
+// from zero on a tie
+#[derive(Debug, Clone, Copy)]
+pub enum Rounding {
+    AwayFromZero,
+    NearestAwayFromZero,
+    NearestEven,
+    NearestTowardZero,
+    Prohibited,
+    TowardGreater,
+    TowardLesser,
+    Truncation,
+}
+
+// an identifier an arithmetic statement stores its result into
+#[derive(Debug)]
+pub struct Receiver {
+    pub ident: Ident,
+    pub rounding: Option<Rounding>,
+}
+
+// the statements of on size error and not on size error. a statement with either of them checks
+// whether each result fits its receiver, and leaves the receiver alone when it doesn't
+#[derive(Debug, Default)]
+pub struct SizeError {
+    pub on: Option<Vec<Instruction>>,
+    pub not_on: Option<Vec<Instruction>>,
+}
+
+impl SizeError {
+    pub fn is_checked(&self) -> bool {
+        self.on.is_some() || self.not_on.is_some()
+    }
+
+    // the statements of both phrases, for passes that look for statements anywhere in a procedure
+    pub fn handlers(&self) -> impl Iterator<Item = &Vec<Instruction>> {
+        self.on.iter().chain(&self.not_on)
+    }
+}
+
+// the receiving side of an arithmetic statement. without giving every receiver is also an
+// operand, and gets the result of combining itself with the sources. with giving the sources are
+// combined with the operand (when there is one) and the result is stored into every receiver.
+// corresponding gives every receiver a source of its own instead
+#[derive(Debug)]
+pub enum Receiving {
+    Receivers(Vec<Receiver>),
+    Giving {
+        operand: Option<Value>,
+        receivers: Vec<Receiver>,
+    },
+    Corresponding(Vec<(Value, Receiver)>),
+}
+
+#[derive(Debug)]
+pub struct Arithmetic {
+    pub sources: Vec<Value>,
+    pub receiving: Receiving,
+    pub size_error: SizeError,
+}
+
+// what an evaluate selects on, every when compares its objects against these in order
+#[derive(Debug)]
+pub enum Subject {
+    Value(Value),
+    Condition(Condition),
+    True,
+    False,
+}
+
+#[derive(Debug)]
+pub enum Object {
+    Any,
+    Value {
+        value: Value,
+        negated: bool,
+    },
+    Range {
+        low: Value,
+        high: Value,
+        negated: bool,
+    },
+    Condition(Condition),
+    True,
+    False,
+}
+
+// one varying (or after) clause of a perform, the first one is the outermost loop
+#[derive(Debug)]
+pub struct Varying {
+    pub ident: Ident,
+    pub from: Value,
+    pub by: Value,
+    pub until: Condition,
+}
+
+// one when branch. stacked whens share their statements, so each of them is a separate list of
+// objects, any of which selects the branch
+#[derive(Debug)]
+pub struct When {
+    pub selections: Vec<Vec<Object>>,
+    pub insts: Vec<Instruction>,
+}
+
+#[derive(Debug)]
+pub enum Instruction {
+    // a move to several receivers is a move to each of them in turn
+    Move(Vec<Infix>),
+    // the moves that initialize expands into, one for every elementary item it sets. a receiver
+    // can go through every entry of the tables it is in
+    Initialize(Vec<Infix>),
+    Add(Arithmetic),
+    // the sources hold the multiplier, and the operand of a giving the multiplicand
+    Multiply(Arithmetic),
+    Subtract(Arithmetic),
+    // the sources hold the divisor, and the operand of a giving the dividend
+    Divide {
+        arithmetic: Arithmetic,
+        remainder: Option<Ident>,
+    },
+    Compute {
+        receivers: Vec<Receiver>,
+        expression: Expression,
+        size_error: SizeError,
+    },
+    Print(Vec<Value>),
+    Repeat {
+        condition: Condition,
+        test_after: bool,
+        insts: Vec<Instruction>,
+    },
+    Varying {
+        varyings: Vec<Varying>,
+        test_after: bool,
+        insts: Vec<Instruction>,
+    },
+    Times {
+        count: Value,
+        insts: Vec<Instruction>,
+    },
+    // an inline perform without a condition, which runs its statements once
+    Inline(Vec<Instruction>),
+    If {
+        condition: Condition,
+        then: Vec<Instruction>,
+        otherwise: Vec<Instruction>,
+    },
+    Evaluate {
+        subjects: Vec<Subject>,
+        whens: Vec<When>,
+        other: Vec<Instruction>,
+    },
+    Perform {
+        from: Arc<str>,
+        thru: Option<Arc<str>>,
+    },
+    SetTrue(Vec<Condition>),
+    GoTo {
+        targets: Vec<Arc<str>>,
+        depending: Option<Value>,
+    },
+    // what the flow pass turns a go to into: leaving the paragraph for the one after it, starting
+    // the paragraph over, or jumping to any procedure through the dispatch loop
+    ExitParagraph,
+    RestartParagraph,
+    ParagraphLoop(Vec<Instruction>),
+    Jump(usize),
+    // exit section is a go to the end of the section, which the flow pass resolves once it knows
+    // where that is. that becomes an exit paragraph in the last paragraph of a section, and a leave
+    // of the last paragraph anywhere else
+    ExitSection,
+    Leave(usize),
+    // depth is how many inline performs deep the statement is, which tells the one it leaves
+    // apart from the ones around it
+    ExitPerform {
+        cycle: bool,
+        depth: usize,
+    },
+    StopRun,
+    Continue,
+    NextSentence,
+    // a sentence that has to be addressable as a whole, right now only because a NEXT SENTENCE
+    // inside of it needs somewhere to jump to
+    Sentence(Vec<Instruction>),
+}
+
+// a paragraph without a name holds the statements between the start of the procedure division (or
+// of a section) and the first paragraph header
+#[derive(Debug)]
+pub struct Paragraph {
+    pub name: Option<Arc<str>>,
+    pub insts: Vec<Instruction>,
+}
+
+// a section without a name holds the paragraphs that come before the first section header
+#[derive(Debug)]
+pub struct Section {
+    pub name: Option<Arc<str>>,
+    pub paragraphs: Vec<Paragraph>,
+}
+
+// words that start a new statement (or a branch of one), used to figure out where the operands of
+// the current one end
+const VERBS: &[&str] = &[
+    "move",
+    "add",
+    "subtract",
+    "multiply",
+    "divide",
+    "compute",
+    "display",
+    "perform",
+    "if",
+    "then",
+    "evaluate",
+    "set",
+    "go",
+    "stop",
+    "goback",
+    "exit",
+    "continue",
+    "next",
+    "initialize",
+];
+
+// words that can follow perform, anything else in that position is the name of a procedure
+const PERFORM_KEYWORDS: &[&str] = &["until", "varying", "with", "test"];
+
+// words that close an open scope. a period closes every open scope, so it lives here as well
+const SCOPE_TERMINATORS: &[&str] = &[
+    ".",
+    "else",
+    "end-if",
+    "end-perform",
+    "when",
+    "end-evaluate",
+    "end-add",
+    "end-subtract",
+    "end-multiply",
+    "end-divide",
+    "end-compute",
+];
+
+pub struct Parser<'a> {
+    contents: &'a str,
+    words: VecDeque<Arc<str>>,
+    look_up: Vec<Data>,
+    in_next_sentence: bool,
+    perform_depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(contents: &'a str) -> Self {
+        Self {
+            contents,
+            words: VecDeque::new(),
+            look_up: vec![],
+            in_next_sentence: false,
+            perform_depth: 0,
+        }
+    }
+
+    pub fn parse(mut self) -> (Vec<Data>, Vec<Section>) {
+        let pro_split: Vec<&str> = self.contents.split("procedure division.").collect();
+        let data_split: Vec<&str> = pro_split[0].split("data division.").collect();
+
+        let procedure = pro_split[1].trim_start();
+        if let Some(data) = data_split.get(1) {
+            let variables = self.parse_data(data.trim_start());
+            self.look_up = variables;
+        }
+        // return-code is a special register every program has, whatever it last held is what the
+        // program exits with. it is a s9(4) comp halfword, so it can go negative
+        if !self.look_up.iter().any(|d| &*d.name == "return-code") {
+            let picture = NumericPicture {
+                signed: true,
+                ..NumericPicture::integer(4)
+            };
+            self.look_up.push(Data {
+                level: 1,
+                name: Arc::from("return-code"),
+                data_type: DataType::Picture(IdentifierType::Numeric(picture)),
+                usage: Usage::Binary,
+                value: None,
+                justified: false,
+                occurs: None,
+                redefines: None,
+                renames: None,
+                conditions: vec![],
+                children: vec![],
+            });
+        }
+        let sections = self.parse_procedure(procedure);
+
+        (self.look_up, sections)
+    }
+
+    fn parse_data(&self, data_segment: &'a str) -> Vec<Data> {
+        let working_storage_split: Vec<&str> =
+            data_segment.split("working-storage section.").collect();
+
+        let Some(working_storage_section) = working_storage_split.get(1) else {
+            return vec![];
+        };
+        // the section is a slice of the source, so where it starts gives the line each word is on,
+        // which diagnostics point at
+        let offset = working_storage_section.as_ptr() as usize - self.contents.as_ptr() as usize;
+        let first_line = self.contents[..offset].matches('\n').count() + 1;
+        let working_storage_words: Vec<(Arc<str>, usize)> = working_storage_section
+            .lines()
+            .enumerate()
+            .flat_map(|(index, line)| {
+                walk_line(line)
+                    .into_iter()
+                    .map(move |word| (word, first_line + index))
+            })
+            .collect();
+        self.parse_working_storage(working_storage_words)
+    }
+
+    // every data description entry ends with a period, and may be spread over several lines. an
+    // entry belongs to the closest entry above it with a lower level number, level 77 entries are
+    // never part of a group, and level 88 entries name conditions of the entry right above them
+    fn parse_working_storage(&self, working_storage_words: Vec<(Arc<str>, usize)>) -> Vec<Data> {
+        let mut variables: Vec<Data> = vec![];
+        // the entry that was parsed last and the groups it is in, innermost last
+        let mut open: Vec<Data> = vec![];
+        for entry in working_storage_words.split(|(w, _)| &**w == ".") {
+            let words: Vec<&str> = entry.iter().map(|(w, _)| &**w).collect();
+            let line = entry.first().map_or(0, |(_, line)| *line);
+            match words.first() {
+                None => {}
+                Some(&"88") => {
+                    let parent = open
+                        .last_mut()
+                        .expect("level 88 entry without a parent item");
+                    parent.conditions.push(parse_condition_name(&words));
+                }
+                Some(&"66") => {
+                    close_groups(&mut open, &mut variables, 1);
+                    let record = variables
+                        .last_mut()
+                        .filter(|record| record.level == 1)
+                        .expect("level 66 entry without a record before it");
+                    let renames = parse_renames(&words, record);
+                    record.children.push(renames);
+                }
+                Some(_) => {
+                    let mut data = self.parse_variable(&words, line);
+                    close_groups(&mut open, &mut variables, nesting(data.level));
+                    if let Some(parent) = open.last() {
+                        if let DataType::Picture(_) = parent.data_type {
+                            panic!(
+                                "{} has a picture, so {} cannot be in it",
+                                parent.name, data.name
+                            );
+                        }
+                        // an item without a usage of its own takes that of its group, which only
+                        // matters to the numeric items in it. a sign clause only matters to the
+                        // signed ones
+                        let takes_usage = match &data.data_type {
+                            DataType::Group => true,
+                            DataType::Picture(IdentifierType::Numeric(picture)) => {
+                                picture.signed || !parent.usage.is_sign()
+                            }
+                            _ => false,
+                        };
+                        if data.usage == Usage::Display && takes_usage {
+                            data.usage = parent.usage;
+                        }
+                    }
+                    open.push(data);
+                }
+            }
+        }
+        close_groups(&mut open, &mut variables, 1);
+
+        // an index name is not part of any record, it is an item of its own holding the number of
+        // the entry it points at
+        let mut indexes = vec![];
+        walk_data(&variables, &mut vec![], &mut |_, data| {
+            if let Some(occurs) = &data.occurs {
+                indexes.extend(occurs.indexes.iter().cloned());
+            }
+        });
+        variables.extend(indexes.into_iter().map(|name| Data {
+            level: 77,
+            name,
+            data_type: DataType::Picture(IdentifierType::Numeric(NumericPicture::integer(9))),
+            usage: Usage::Binary,
+            value: None,
+            justified: false,
+            occurs: None,
+            redefines: None,
+            renames: None,
+            conditions: vec![],
+            children: vec![],
+        }));
+
+        variables
+    }
+
+    // level [name | filler], followed by its clauses in any order: redefines name, pic[ture] [is]
+    // picture, [usage [is]] usage, occurs, just[ified] [right], value [is] literal,
+    // sync[hronized] [left | right] and [sign [is]] leading | trailing [separate [character]]. an
+    // entry without a name is a filler
+    fn parse_variable(&self, words: &[&str], line: usize) -> Data {
+        let (name, clauses) = match words.get(1) {
+            Some(word) if !is_data_clause(word) => (Arc::from(*word), &words[2..]),
+            _ => (Arc::from("filler"), &words[1..]),
+        };
+        let level = match words[0].parse() {
+            Ok(level @ (1..=49 | 77)) => level,
+            _ => panic!(
+                "line {line}: {name} has {}, which is not a level number",
+                words[0]
+            ),
+        };
+        let mut data_type = DataType::Group;
+        let mut usage = Usage::Display;
+        let mut sign = None;
+        let mut occurs = None;
+        let mut redefines = None;
+        let mut value = None;
+        let mut justified = false;
+
+        let mut clauses = clauses.iter().copied().peekable();
+        while let Some(word) = clauses.next() {
+            match word {
+                "pic" | "picture" => {
+                    clauses.next_if_eq(&"is");
+                    let picture = clauses.next().expect("picture clause without a picture");
+                    let kind = IdentifierType::parse_type(picture).unwrap_or_else(|| {
+                        panic!("line {line}: {name} has picture {picture}, which is not supported")
+                    });
+                    data_type = DataType::Picture(kind);
+                }
+                "usage" => {
+                    clauses.next_if_eq(&"is");
+                    let word = clauses.next().expect("usage clause without a usage");
+                    usage = Usage::parse(word).unwrap_or_else(|| {
+                        panic!("line {line}: {name} has usage {word}, which is not supported")
+                    });
+                }
+                "redefines" if level == 77 => {
+                    panic!("{name} is a level 77 item and cannot redefine")
+                }
+                "redefines" => {
+                    let name = clauses.next().expect("redefines clause without an item");
+                    redefines = Some(Arc::from(name));
+                }
+                "value" => {
+                    clauses.next_if_eq(&"is");
+                    let literal = match clauses.next() {
+                        Some("all") => format!("all {}", clauses.next().unwrap_or_default()),
+                        Some(literal) => String::from(literal),
+                        None => panic!("value clause of {name} without a literal"),
+                    };
+                    value = Some(Value::derive(&literal));
+                }
+                "justified" | "just" => {
+                    clauses.next_if_eq(&"right");
+                    justified = true;
+                }
+                "occurs" if nesting(level) == 1 => panic!("{name} is a record and cannot occur"),
+                "occurs" => occurs = Some(parse_occurs(&mut clauses)),
+                // synchronized only asks for binary items to be aligned on a word boundary, which
+                // the layout never does, so the item takes the same bytes either way
+                "sync" | "synchronized" => {
+                    clauses.next_if(|word| matches!(*word, "left" | "right"));
+                }
+                "sign" => {
+                    clauses.next_if_eq(&"is");
+                    match clauses.next() {
+                        Some(position @ ("leading" | "trailing")) => {
+                            sign = Some(parse_sign(position, &mut clauses))
+                        }
+                        _ => {
+                            panic!("line {line}: sign clause of {name} without leading or trailing")
+                        }
+                    }
+                }
+                "leading" | "trailing" => sign = Some(parse_sign(word, &mut clauses)),
+                word => match Usage::parse(word) {
+                    Some(parsed) => usage = parsed,
+                    None => {
+                        panic!("line {line}: {name} has a {word} clause, which is not supported")
+                    }
+                },
+            }
+        }
+
+        // the sign clause is where a display item keeps its sign, which only a signed numeric item
+        // or a group of them has
+        if let Some(sign) = sign {
+            let signed = match &data_type {
+                DataType::Group => true,
+                DataType::Picture(IdentifierType::Numeric(picture)) => picture.signed,
+                _ => false,
+            };
+            if !signed {
+                panic!("line {line}: {name} is not a signed numeric item and cannot have a sign clause");
+            }
+            if usage != Usage::Display {
+                panic!("line {line}: {name} has a sign clause, so it can only be usage display");
+            }
+            usage = sign;
+        }
+
+        // the usage of a group is passed down to the numeric items in it
+        let numeric = matches!(
+            data_type,
+            DataType::Group | DataType::Picture(IdentifierType::Numeric(_))
+        );
+        if usage != Usage::Display && !numeric {
+            panic!("{name} is neither a numeric item nor a group and can only be usage display");
+        }
+        let text = matches!(
+            data_type,
+            DataType::Picture(IdentifierType::Alphabetic(_) | IdentifierType::Alphanumeric(_))
+        );
+        if justified && !text {
+            panic!("{name} is not an alphabetic or alphanumeric item and cannot be justified");
+        }
+        if value.is_some() && redefines.is_some() {
+            panic!("{name} redefines another item, so it cannot have a value");
+        }
+
+        Data {
+            level,
+            name,
+            data_type,
+            usage,
+            value,
+            justified,
+            occurs,
+            redefines,
+            renames: None,
+            conditions: vec![],
+            children: vec![],
+        }
+    }
+
+    fn parse_procedure(&mut self, procedure: &'a str) -> Vec<Section> {
+        let mut sections = vec![Section {
+            name: None,
+            paragraphs: vec![],
+        }];
+        let words = join_subscripts(procedure.lines().flat_map(walk_line), &self.look_up);
+        self.words = join_qualified(words.into_iter());
+
+        while !self.words.is_empty() {
+            if let Some(name) = self.parse_section_header() {
+                sections.push(Section {
+                    name: Some(name),
+                    paragraphs: vec![],
+                });
+                continue;
+            }
+
+            let section = sections.last_mut().unwrap();
+            if let Some(name) = self.parse_paragraph_header() {
+                section.paragraphs.push(Paragraph {
+                    name: Some(name),
+                    insts: vec![],
+                });
+                continue;
+            }
+
+            if section.paragraphs.is_empty() {
+                section.paragraphs.push(Paragraph {
+                    name: None,
+                    insts: vec![],
+                });
+            }
+            let sentence = self.parse_sentence();
+            let paragraph = sections.last_mut().unwrap().paragraphs.last_mut().unwrap();
+            paragraph.insts.extend(sentence);
+        }
+
+        sections
+    }
+
+    // name section.
+    fn parse_section_header(&mut self) -> Option<Arc<str>> {
+        // exit section. is a statement, not the header of a section named exit
+        let is_header = self.words.get(1).is_some_and(|w| &**w == "section")
+            && self.words.get(2).is_some_and(|w| &**w == ".")
+            && self.words.front().is_some_and(|w| !VERBS.contains(&&**w));
+        if !is_header {
+            return None;
+        }
+
+        let name = self.words.pop_front();
+        self.words.drain(..2);
+        name
+    }
+
+    // name.
+    fn parse_paragraph_header(&mut self) -> Option<Arc<str>> {
+        let name = self.words.front()?;
+        let is_header = self.words.get(1).is_some_and(|w| &**w == ".")
+            && !VERBS.contains(&&**name)
+            && !SCOPE_TERMINATORS.contains(&&**name);
+        if !is_header {
+            return None;
+        }
+
+        let name = self.words.pop_front();
+        self.words.pop_front();
+        name
+    }
+
+    // a sentence is every statement up to and including the next period
+    fn parse_sentence(&mut self) -> Vec<Instruction> {
+        self.in_next_sentence = false;
+        let instructions = self.parse_statements(&[]);
+        if let Some(word) = self.words.pop_front() {
+            if &*word != "." {
+                panic!("unexpected {word}");
+            }
+        }
+
+        if self.in_next_sentence {
+            vec![Instruction::Sentence(instructions)]
+        } else {
+            instructions
+        }
+    }
+
+    // parses statements until a period or one of the given terminators, neither of which is
+    // consumed, so the caller can decide what the terminator means for its scope
+    fn parse_statements(&mut self, terminators: &[&str]) -> Vec<Instruction> {
+        let mut instructions = vec![];
+        while let Some(word) = self.words.front() {
+            if &**word == "." || terminators.contains(&&**word) || self.at_size_error(true) {
+                break;
+            }
+
+            let instruction = self.generate_instruction();
+            instructions.push(instruction);
+        }
+
+        instructions
+    }
+
+    // takes every word up to the start of the next statement or the end of the current scope
+    fn take_operands(&mut self) -> Vec<Arc<str>> {
+        let mut operands = vec![];
+        while let Some(word) = self.words.front() {
+            if VERBS.contains(&&**word)
+                || SCOPE_TERMINATORS.contains(&&**word)
+                || self.at_size_error(false)
+                || self.at_size_error(true)
+            {
+                break;
+            }
+            operands.push(self.words.pop_front().unwrap());
+        }
+
+        operands
+    }
+
+    fn generate_instruction(&mut self) -> Instruction {
+        let instruction = self.words.pop_front().unwrap();
+        let words = self.take_operands();
+        let operands: Vec<&str> = words.iter().map(|w| &**w).collect();
+
+        match &*instruction {
+            "move" => self.generate_move(&operands),
+            "add" => self.generate_add(&operands),
+            "subtract" => self.generate_subtract(&operands),
+            "multiply" => self.generate_multiply(&operands),
+            "divide" => self.generate_divide(&operands),
+            "compute" => self.generate_compute(&operands),
+            "display" => self.generate_print(&operands),
+            "perform" => self.generate_perform(&operands),
+            "if" => self.generate_if(&operands),
+            "evaluate" => self.generate_evaluate(&operands),
+            "set" => self.generate_set(&operands),
+            "go" => self.generate_go(&operands),
+            "stop" | "goback" | "exit" | "continue" => self.generate_exit(&instruction, &operands),
+            "next" => self.generate_next(&operands),
+            "initialize" => self.generate_initialize(&operands),
+            _ => panic!("unimplemented instruction {instruction}"),
+        }
+    }
+
+    fn generate_print(&self, operands: &[&str]) -> Instruction {
+        let values: Vec<Value> = operands
+            .iter()
+            .map(|o| resolve_value(&self.look_up, o))
+            .collect();
+
+        Instruction::Print(values)
+    }
+
+    // move value to identifier... | move corr[esponding] group to group...
+    fn generate_move(&self, operands: &[&str]) -> Instruction {
+        if let ["corresponding" | "corr", group, "to", receivers @ ..] = operands {
+            let moves = receivers
+                .iter()
+                .flat_map(|receiver| corresponding(&self.look_up, "move", group, receiver, false))
+                .map(|(sending, receiving)| Infix {
+                    left: Value::Identifier(sending),
+                    right: receiving,
+                })
+                .collect();
+            return Instruction::Move(moves);
+        }
+
+        let [value, "to", receivers @ ..] = operands else {
+            panic!("unsupported move {}", operands.join(" "));
+        };
+        if receivers.is_empty() {
+            panic!("move without a receiver");
+        }
+
+        let value = resolve_value(&self.look_up, value);
+        Instruction::Move(moves_to(&self.look_up, &value, receivers))
+    }
+
+    // add value... to identifier...
+    // | add value... [to value] giving identifier...
+    // | add corr[esponding] group to group [rounded]
+    fn generate_add(&mut self, operands: &[&str]) -> Instruction {
+        if let ["corresponding" | "corr", group, "to", receiver @ ..] = operands {
+            return Instruction::Add(Arithmetic {
+                sources: vec![],
+                receiving: self.corresponding_receiving("add", group, receiver),
+                size_error: self.parse_size_error("end-add"),
+            });
+        }
+
+        let resolve = |word: &&str| resolve_value(&self.look_up, word);
+        let giving = operands.iter().position(|w| *w == "giving");
+        let to = operands.iter().position(|w| *w == "to");
+        let (sources, receiving) = match (to, giving) {
+            (Some(to), None) => (
+                &operands[..to],
+                Receiving::Receivers(resolve_receivers(&self.look_up, &operands[to + 1..])),
+            ),
+            (to, Some(giving)) => {
+                let (sources, operand) = match to {
+                    Some(to) => {
+                        let [operand] = &operands[to + 1..giving] else {
+                            panic!("add giving takes a single operand after to");
+                        };
+                        (&operands[..to], Some(resolve(operand)))
+                    }
+                    None => (&operands[..giving], None),
+                };
+                (
+                    sources,
+                    Receiving::Giving {
+                        operand,
+                        receivers: resolve_receivers(&self.look_up, &operands[giving + 1..]),
+                    },
+                )
+            }
+            (None, None) => panic!("add without to or giving {}", operands.join(" ")),
+        };
+        if sources.is_empty() {
+            panic!("add without anything to add {}", operands.join(" "));
+        }
+        let sources = sources.iter().map(resolve).collect();
+
+        Instruction::Add(Arithmetic {
+            sources,
+            receiving,
+            size_error: self.parse_size_error("end-add"),
+        })
+    }
+
+    // multiply value by identifier...
+    // | multiply value by value giving identifier...
+    fn generate_multiply(&mut self, operands: &[&str]) -> Instruction {
+        let (multiplier, receiving) = match operands {
+            [multiplier, "by", multiplicand, "giving", receivers @ ..] => (
+                multiplier,
+                Receiving::Giving {
+                    operand: Some(resolve_value(&self.look_up, multiplicand)),
+                    receivers: resolve_receivers(&self.look_up, receivers),
+                },
+            ),
+            [multiplier, "by", receivers @ ..] => (
+                multiplier,
+                Receiving::Receivers(resolve_receivers(&self.look_up, receivers)),
+            ),
+            _ => panic!("unsupported multiply {}", operands.join(" ")),
+        };
+
+        Instruction::Multiply(Arithmetic {
+            sources: vec![resolve_value(&self.look_up, multiplier)],
+            receiving,
+            size_error: self.parse_size_error("end-multiply"),
+        })
+    }
+
+    // subtract value... from identifier...
+    // | subtract value... from value giving identifier...
+    // | subtract corr[esponding] group from group [rounded]
+    fn generate_subtract(&mut self, operands: &[&str]) -> Instruction {
+        if let ["corresponding" | "corr", group, "from", receiver @ ..] = operands {
+            return Instruction::Subtract(Arithmetic {
+                sources: vec![],
+                receiving: self.corresponding_receiving("subtract", group, receiver),
+                size_error: self.parse_size_error("end-subtract"),
+            });
+        }
+
+        let Some(from) = operands.iter().position(|w| *w == "from") else {
+            panic!("subtract without from {}", operands.join(" "));
+        };
+        let sources = operands[..from]
+            .iter()
+            .map(|w| resolve_value(&self.look_up, w))
+            .collect();
+        let rest = &operands[from + 1..];
+        let receiving = match rest.iter().position(|w| *w == "giving") {
+            Some(giving) => {
+                let [minuend] = &rest[..giving] else {
+                    panic!(
+                        "subtract giving needs a single minuend {}",
+                        operands.join(" ")
+                    );
+                };
+                Receiving::Giving {
+                    operand: Some(resolve_value(&self.look_up, minuend)),
+                    receivers: resolve_receivers(&self.look_up, &rest[giving + 1..]),
+                }
+            }
+            None => Receiving::Receivers(resolve_receivers(&self.look_up, rest)),
+        };
+
+        Instruction::Subtract(Arithmetic {
+            sources,
+            receiving,
+            size_error: self.parse_size_error("end-subtract"),
+        })
+    }
+
+    // the receiving group of add or subtract corresponding, whose numeric items are each given the
+    // one of the same name in the other group as their source. rounded applies to all of them
+    fn corresponding_receiving(&self, statement: &str, group: &str, words: &[&str]) -> Receiving {
+        let [receiver] = &resolve_receivers(&self.look_up, words)[..] else {
+            panic!("{statement} corresponding takes a single receiving group");
+        };
+        let pairs = corresponding(&self.look_up, statement, group, words[0], true);
+        Receiving::Corresponding(
+            pairs
+                .into_iter()
+                .map(|(source, ident)| {
+                    let rounding = receiver.rounding;
+                    (Value::Identifier(source), Receiver { ident, rounding })
+                })
+                .collect(),
+        )
+    }
+
+    // divide value into identifier...
+    // | divide value into|by value giving identifier... [remainder identifier]
+    fn generate_divide(&mut self, operands: &[&str]) -> Instruction {
+        let resolve = |word: &&str| resolve_value(&self.look_up, word);
+        let (divisor, receiving, remainder) = match operands {
+            [divisor, "into", rest @ ..] if !rest.contains(&"giving") => (
+                resolve(divisor),
+                Receiving::Receivers(resolve_receivers(&self.look_up, rest)),
+                None,
+            ),
+            [left, way @ ("into" | "by"), right, "giving", rest @ ..] => {
+                let (dividend, divisor) = if *way == "into" {
+                    (right, left)
+                } else {
+                    (left, right)
+                };
+                let (receivers, remainder) = match rest {
+                    [receivers @ .., "remainder", remainder] => {
+                        let receivers = resolve_receivers(&self.look_up, receivers);
+                        if receivers.len() != 1 {
+                            panic!("divide with remainder needs a single quotient");
+                        }
+                        (receivers, Some(resolve_receiver(&self.look_up, remainder)))
+                    }
+                    _ => (resolve_receivers(&self.look_up, rest), None),
+                };
+
+                (
+                    resolve(divisor),
+                    Receiving::Giving {
+                        operand: Some(resolve(dividend)),
+                        receivers,
+                    },
+                    remainder,
+                )
+            }
+            _ => panic!("unsupported divide {}", operands.join(" ")),
+        };
+
+        Instruction::Divide {
+            arithmetic: Arithmetic {
+                sources: vec![divisor],
+                receiving,
+                size_error: self.parse_size_error("end-divide"),
+            },
+            remainder,
+        }
+    }
+
+    // [on] size error statements... [not [on] size error statements...] [end-verb]
+    fn parse_size_error(&mut self, terminator: &str) -> SizeError {
+        let mut size_error = SizeError::default();
+        if self.skip_size_error(false) {
+            size_error.on = Some(self.parse_statements(SCOPE_TERMINATORS));
+        }
+        if self.skip_size_error(true) {
+            size_error.not_on = Some(self.parse_statements(SCOPE_TERMINATORS));
+        }
+
+        if self.words.front().is_some_and(|w| &**w == terminator) {
+            self.words.pop_front();
+        }
+
+        size_error
+    }
+
+    fn at_size_error(&self, negated: bool) -> bool {
+        let words: Vec<&str> = self.words.iter().take(4).map(|w| &**w).collect();
+        let words = match (negated, &words[..]) {
+            (true, ["not", rest @ ..]) => rest,
+            (false, words) => words,
+            _ => return false,
+        };
+
+        matches!(words, ["on", "size", "error", ..] | ["size", "error", ..])
+    }
+
+    fn skip_size_error(&mut self, negated: bool) -> bool {
+        if !self.at_size_error(negated) {
+            return false;
+        }
+
+        while let Some(word) = self.words.pop_front() {
+            if &*word == "error" {
+                break;
+            }
+        }
+        true
+    }
+
+    // compute identifier [rounded]... =|equal expression
+    fn generate_compute(&mut self, operands: &[&str]) -> Instruction {
+        let Some(equals) = operands.iter().position(|w| *w == "=" || *w == "equal") else {
+            panic!("compute without = {}", operands.join(" "));
+        };
+
+        let receivers = resolve_receivers(&self.look_up, &operands[..equals]);
+        let expression = parse_expression(&self.look_up, &operands[equals + 1..]);
+        let size_error = self.parse_size_error("end-compute");
+
+        Instruction::Compute {
+            receivers,
+            expression,
+            size_error,
+        }
+    }
+
+    // perform [procedure [thru procedure]]
+    //     [count times | [with test before|after] until ... | [with test ...] varying ...]
+    fn generate_perform(&mut self, operands: &[&str]) -> Instruction {
+        let (procedure, rest) = match operands {
+            [_, "times"] => (None, operands),
+            [name, "thru" | "through", thru, rest @ ..] if !PERFORM_KEYWORDS.contains(name) => (
+                Some(Instruction::Perform {
+                    from: Arc::from(*name),
+                    thru: Some(Arc::from(*thru)),
+                }),
+                rest,
+            ),
+            [name, rest @ ..] if !PERFORM_KEYWORDS.contains(name) => (
+                Some(Instruction::Perform {
+                    from: Arc::from(*name),
+                    thru: None,
+                }),
+                rest,
+            ),
+            _ => (None, operands),
+        };
+
+        let (test_after, rest) = match rest {
+            ["with", "test", test, rest @ ..] | ["test", test, rest @ ..] => {
+                (*test == "after", rest)
+            }
+            _ => (false, rest),
+        };
+
+        match rest {
+            ["until", condition @ ..] => {
+                let condition = parse_condition(&self.look_up, condition);
+                Instruction::Repeat {
+                    condition,
+                    test_after,
+                    insts: self.perform_body(procedure),
+                }
+            }
+            ["varying", clauses @ ..] => {
+                let varyings = clauses
+                    .split(|w| *w == "after")
+                    .map(|clause| self.parse_varying(clause))
+                    .collect();
+                Instruction::Varying {
+                    varyings,
+                    test_after,
+                    insts: self.perform_body(procedure),
+                }
+            }
+            [count, "times"] => Instruction::Times {
+                count: resolve_value(&self.look_up, count),
+                insts: self.perform_body(procedure),
+            },
+            [] => match procedure {
+                Some(procedure) => procedure,
+                None => Instruction::Inline(self.perform_body(None)),
+            },
+            _ => unimplemented!(),
+        }
+    }
+
+    // an out of line perform runs its procedure, an inline one the statements up to end-perform
+    fn perform_body(&mut self, procedure: Option<Instruction>) -> Vec<Instruction> {
+        if let Some(procedure) = procedure {
+            return vec![procedure];
+        }
+
+        self.perform_depth += 1;
+        let instructions = self.parse_statements(&["end-perform"]);
+        self.perform_depth -= 1;
+        match self.words.pop_front().as_deref() {
+            Some("end-perform") => {}
+            _ => panic!("Missing corresponding 'end-perform'"),
+        }
+
+        instructions
+    }
+
+    // identifier from value by value until condition
+    fn parse_varying(&self, words: &[&str]) -> Varying {
+        let [ident, "from", from, "by", by, "until", until @ ..] = words else {
+            panic!("unsupported varying {}", words.join(" "));
+        };
+
+        let Value::Identifier(ident) = resolve_value(&self.look_up, ident) else {
+            panic!("varying {ident} is not an identifier");
+        };
+
+        Varying {
+            ident,
+            from: resolve_value(&self.look_up, from),
+            by: resolve_value(&self.look_up, by),
+            until: parse_condition(&self.look_up, until),
+        }
+    }
+
+    fn generate_if(&mut self, operands: &[&str]) -> Instruction {
+        let condition = parse_condition(&self.look_up, operands);
+        if self.words.front().is_some_and(|w| &**w == "then") {
+            self.words.pop_front();
+        }
+
+        let then = self.parse_statements(&["else", "end-if"]);
+        let otherwise = if self.words.front().is_some_and(|w| &**w == "else") {
+            self.words.pop_front();
+            self.parse_statements(&["end-if"])
+        } else {
+            vec![]
+        };
+
+        // a period ends the if as well, but it also ends every other open scope, so it is left for
+        // the sentence to consume
+        if self.words.front().is_some_and(|w| &**w == "end-if") {
+            self.words.pop_front();
+        }
+
+        Instruction::If {
+            condition,
+            then,
+            otherwise,
+        }
+    }
+
+    fn generate_evaluate(&mut self, operands: &[&str]) -> Instruction {
+        let subjects: Vec<Subject> = operands
+            .split(|w| *w == "also")
+            .map(|subject| match subject {
+                ["true"] => Subject::True,
+                ["false"] => Subject::False,
+                [word] => Subject::Value(resolve_value(&self.look_up, word)),
+                words => Subject::Condition(parse_condition(&self.look_up, words)),
+            })
+            .collect();
+
+        let mut whens = vec![];
+        let mut other = vec![];
+        let mut selections = vec![];
+        while self.words.front().is_some_and(|w| &**w == "when") {
+            self.words.pop_front();
+            let words = self.take_operands();
+            let objects: Vec<&str> = words.iter().map(|w| &**w).collect();
+
+            if objects == ["other"] {
+                other = self.parse_statements(&["when", "end-evaluate"]);
+                break;
+            }
+
+            let objects: Vec<&[&str]> = objects.split(|w| *w == "also").collect();
+            if objects.len() != subjects.len() {
+                panic!("when has a different number of objects than its evaluate has subjects");
+            }
+            selections.push(
+                objects
+                    .iter()
+                    .zip(&subjects)
+                    .map(|(object, subject)| self.parse_object(object, subject))
+                    .collect(),
+            );
+
+            // a when directly followed by another one shares its statements
+            let insts = self.parse_statements(&["when", "end-evaluate"]);
+            if insts.is_empty() && self.words.front().is_some_and(|w| &**w == "when") {
+                continue;
+            }
+            whens.push(When {
+                selections: std::mem::take(&mut selections),
+                insts,
+            });
+        }
+
+        if self.words.front().is_some_and(|w| &**w == "end-evaluate") {
+            self.words.pop_front();
+        }
+
+        Instruction::Evaluate {
+            subjects,
+            whens,
+            other,
+        }
+    }
+
+    // any | true | false | condition | [not] value [thru value]
+    fn parse_object(&self, words: &[&str], subject: &Subject) -> Object {
+        match (words, subject) {
+            (["any"], _) => Object::Any,
+            (["true"], _) => Object::True,
+            (["false"], _) => Object::False,
+            (_, Subject::True | Subject::False) => {
+                Object::Condition(parse_condition(&self.look_up, words))
+            }
+            _ => {
+                let (negated, words) = match words {
+                    ["not", rest @ ..] => (true, rest),
+                    _ => (false, words),
+                };
+
+                match words {
+                    [value] => Object::Value {
+                        value: resolve_value(&self.look_up, value),
+                        negated,
+                    },
+                    [low, "thru" | "through", high] => Object::Range {
+                        low: resolve_value(&self.look_up, low),
+                        high: resolve_value(&self.look_up, high),
+                        negated,
+                    },
+                    _ => panic!("unsupported when {}", words.join(" ")),
+                }
+            }
+        }
+    }
+
+    // set condition-name... to true | set index... to value | set index... up|down by value. an
+    // index holds the number of an entry, so setting it is a move and stepping it an add
+    fn generate_set(&self, operands: &[&str]) -> Instruction {
+        match operands {
+            [indexes @ .., "to", value] if *value != "true" => {
+                let value = resolve_value(&self.look_up, value);
+                Instruction::Move(moves_to(&self.look_up, &value, indexes))
+            }
+            [indexes @ .., direction @ ("up" | "down"), "by", value] => {
+                let arithmetic = Arithmetic {
+                    sources: vec![resolve_value(&self.look_up, value)],
+                    receiving: Receiving::Receivers(resolve_receivers(&self.look_up, indexes)),
+                    size_error: SizeError::default(),
+                };
+                if *direction == "up" {
+                    Instruction::Add(arithmetic)
+                } else {
+                    Instruction::Subtract(arithmetic)
+                }
+            }
+            [names @ .., "to", "true"] => Instruction::SetTrue(
+                names
+                    .iter()
+                    .map(|name| {
+                        find_condition_name(&self.look_up, name)
+                            .unwrap_or_else(|| panic!("{name} is not a condition name"))
+                    })
+                    .collect(),
+            ),
+            _ => panic!("unsupported set {}", operands.join(" ")),
+        }
+    }
+
+    // initialize identifier... [[with] filler] [all | category to value] [then] [replacing
+    // category [data] by value...] [then] [to default]. every elementary item of the identifiers
+    // takes the first of these that applies to it: its own value clause, the replacement for its
+    // category, or the default, which is spaces for text and zero for numbers. only to default, or
+    // none of the phrases at all, fall back on the default, and otherwise an item none of them
+    // apply to is left alone. fillers are only set with filler, and items that redefine or rename
+    // others never are, their bytes belong to the items they share them with
+    fn generate_initialize(&mut self, operands: &[&str]) -> Instruction {
+        // then is a verb of its own, so the phrases after it are not among the operands yet
+        let mut phrases = vec![];
+        while self.words.front().is_some_and(|w| &**w == "then")
+            && self
+                .words
+                .get(1)
+                .is_some_and(|w| matches!(&**w, "replacing" | "to"))
+        {
+            self.words.pop_front();
+            phrases.extend(self.take_operands());
+        }
+
+        let mut words = operands
+            .iter()
+            .copied()
+            .chain(phrases.iter().map(|w| &**w))
+            .peekable();
+        let identifiers: Vec<&str> = std::iter::from_fn(|| {
+            words.next_if(|w| {
+                !matches!(*w, "with" | "filler" | "all" | "replacing" | "to")
+                    && Category::parse(w).is_none()
+            })
+        })
+        .collect();
+        if identifiers.is_empty() {
+            panic!("initialize without an identifier");
+        }
+
+        fn expect<'w>(words: &mut impl Iterator<Item = &'w str>, expected: &[&str]) {
+            for word in expected {
+                if words.next() != Some(word) {
+                    panic!("initialize expects {}", expected.join(" "));
+                }
+            }
+        }
+        let mut initializing = Initializing::default();
+        while let Some(word) = words.next() {
+            match word {
+                "with" | "filler" => {
+                    if word == "with" {
+                        expect(&mut words, &["filler"]);
+                    }
+                    initializing.filler = true;
+                }
+                "all" => {
+                    expect(&mut words, &["to", "value"]);
+                    initializing.values.extend(Category::ALL);
+                }
+                "replacing" => {
+                    while let Some(category) = words.peek().and_then(|w| Category::parse(w)) {
+                        words.next();
+                        words.next_if_eq(&"data");
+                        expect(&mut words, &["by"]);
+                        let value = words.next().expect("replacing without a value");
+                        initializing
+                            .replacing
+                            .push((category, resolve_value(&self.look_up, value)));
+                    }
+                }
+                "to" => {
+                    expect(&mut words, &["default"]);
+                    initializing.default = true;
+                }
+                _ => match Category::parse(word) {
+                    Some(category) => {
+                        expect(&mut words, &["to", "value"]);
+                        initializing.values.push(category);
+                    }
+                    None => panic!("unexpected {word} in initialize"),
+                },
+            }
+        }
+        if initializing.values.is_empty() && initializing.replacing.is_empty() {
+            initializing.default = true;
+        }
+
+        let mut moves = vec![];
+        for identifier in identifiers {
+            let (mut ancestors, data, ident) = find_item(&self.look_up, identifier)
+                .unwrap_or_else(|| panic!("{identifier} is not defined"));
+            initialize_item(
+                &self.look_up,
+                &initializing,
+                &mut ancestors,
+                &mut ident.path.clone(),
+                (ident.name.clone(), data),
+                &ident,
+                &mut moves,
+            );
+        }
+
+        Instruction::Initialize(moves)
+    }
+
+    // go [to] procedure | go [to] procedure... depending [on] identifier
+    fn generate_go(&self, operands: &[&str]) -> Instruction {
+        let operands = match operands {
+            ["to", rest @ ..] => rest,
+            _ => operands,
+        };
+
+        match operands {
+            [target] => Instruction::GoTo {
+                targets: vec![Arc::from(*target)],
+                depending: None,
+            },
+            [targets @ .., "depending", "on", ident] | [targets @ .., "depending", ident]
+                if !targets.is_empty() =>
+            {
+                Instruction::GoTo {
+                    targets: targets.iter().map(|t| Arc::from(*t)).collect(),
+                    depending: Some(resolve_value(&self.look_up, ident)),
+                }
+            }
+            _ => panic!("unsupported go to {}", operands.join(" ")),
+        }
+    }
+
+    fn generate_exit(&mut self, verb: &str, operands: &[&str]) -> Instruction {
+        // perform is a verb, so the operands of an exit perform stop right before it
+        if verb == "exit"
+            && operands.is_empty()
+            && self.words.front().is_some_and(|w| &**w == "perform")
+        {
+            self.words.pop_front();
+            if self.perform_depth == 0 {
+                panic!("exit perform outside of an inline perform");
+            }
+            let cycle = self.words.front().is_some_and(|w| &**w == "cycle");
+            if cycle {
+                self.words.pop_front();
+            }
+            return Instruction::ExitPerform {
+                cycle,
+                depth: self.perform_depth,
+            };
+        }
+
+        match (verb, operands) {
+            ("stop", ["run"]) | ("goback", []) => Instruction::StopRun,
+            // exit program only leaves a called program, and the ones we generate never are. a
+            // plain exit is what marks the end of a paragraph that only exists to be gone to
+            ("exit", ["program"]) | ("exit", []) | ("continue", []) => Instruction::Continue,
+            ("exit", ["paragraph"]) => Instruction::ExitParagraph,
+            ("exit", ["section"]) => Instruction::ExitSection,
+            _ => panic!("unsupported {verb} {}", operands.join(" ")),
+        }
+    }
+
+    fn generate_next(&mut self, operands: &[&str]) -> Instruction {
+        match operands {
+            ["sentence"] => {
+                self.in_next_sentence = true;
+                Instruction::NextSentence
+            }
+            _ => panic!("expected 'next sentence'"),
+        }
+    }
+}
+
+// recursive descent over the words of a condition. `subject` and `relation` remember the last
+// relation parsed, which is what an abbreviated combined condition like `a > b and < c` or
+// `a = b or c` borrows its missing parts from
+struct ConditionParser<'b> {
+    look_up: &'b [Data],
+    words: Vec<&'b str>,
+    position: usize,
+    subject: Option<Value>,
+    relation: Option<Relation>,
+}
+
+impl<'b> ConditionParser<'b> {
+    fn new(look_up: &'b [Data], operands: &[&'b str]) -> Self {
+        Self {
+            look_up,
+            words: split_parentheses(operands),
+            position: 0,
+            subject: None,
+            relation: None,
+        }
+    }
+
+    fn peek(&self) -> Option<&'b str> {
+        self.words.get(self.position).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&'b str> {
+        self.words.get(self.position + offset).copied()
+    }
+
+    fn next(&mut self) -> Option<&'b str> {
+        let word = self.peek();
+        self.position += 1;
+        word
+    }
+
+    fn skip(&mut self, word: &str) -> bool {
+        if self.peek() == Some(word) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse(mut self) -> Condition {
+        let condition = self.parse_or();
+        if let Some(word) = self.peek() {
+            panic!("unexpected {word} in condition {}", self.words.join(" "));
+        }
+
+        condition
+    }
+
+    fn parse_or(&mut self) -> Condition {
+        let mut condition = self.parse_and();
+        while self.skip("or") {
+            let right = self.parse_and();
+            condition = Condition::Or(Box::new(condition), Box::new(right));
+        }
+
+        condition
+    }
+
+    fn parse_and(&mut self) -> Condition {
+        let mut condition = self.parse_not();
+        while self.skip("and") {
+            let right = self.parse_not();
+            condition = Condition::And(Box::new(condition), Box::new(right));
+        }
+
+        condition
+    }
+
+    fn parse_not(&mut self) -> Condition {
+        // `not` in front of a relational operator negates the operator of an abbreviated
+        // relation rather than a whole condition
+        if self.peek() == Some("not") && !self.at_relation_at(1) {
+            self.position += 1;
+            return Condition::Not(Box::new(self.parse_not()));
+        }
+
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Condition {
+        if self.skip("(") {
+            let condition = self.parse_or();
+            if !self.skip(")") {
+                panic!("missing ) in condition {}", self.words.join(" "));
+            }
+            return condition;
+        }
+
+        if let Some(condition) = self
+            .peek()
+            .and_then(|word| find_condition_name(self.look_up, word))
+        {
+            self.position += 1;
+            return condition;
+        }
+
+        // `and < c`: the subject is missing
+        if self.at_relation() {
+            let subject = self
+                .subject
+                .clone()
+                .expect("abbreviated condition without a subject");
+            let relation = self.parse_relation();
+            let right = self.parse_operand();
+            return self.relation(subject, relation, right);
+        }
+
+        let left = self.parse_operand();
+        if let Some(condition) = self.parse_class_or_sign(&left) {
+            return condition;
+        }
+
+        if self.at_relation() {
+            let relation = self.parse_relation();
+            let right = self.parse_operand();
+            return self.relation(left, relation, right);
+        }
+
+        // `or c`: both the subject and the operator are missing
+        let (Some(subject), Some(relation)) = (self.subject.clone(), self.relation) else {
+            panic!("incomplete condition {}", self.words.join(" "));
+        };
+        Condition::Relation {
+            left: subject,
+            relation,
+            right: left,
+        }
+    }
+
+    fn relation(&mut self, left: Value, relation: Relation, right: Value) -> Condition {
+        self.subject = Some(left.clone());
+        self.relation = Some(relation);
+        Condition::Relation {
+            left,
+            relation,
+            right,
+        }
+    }
+
+    // [is] [not] numeric | alphabetic | alphabetic-lower | alphabetic-upper | positive | negative
+    // | zero
+    fn parse_class_or_sign(&mut self, value: &Value) -> Option<Condition> {
+        let mut offset = 0;
+        if self.peek_at(offset) == Some("is") {
+            offset += 1;
+        }
+        let negated = self.peek_at(offset) == Some("not");
+        if negated {
+            offset += 1;
+        }
+
+        let value = value.clone();
+        let condition = match self.peek_at(offset)? {
+            "numeric" => Condition::Class {
+                value,
+                class: Class::Numeric,
+            },
+            "alphabetic" => Condition::Class {
+                value,
+                class: Class::Alphabetic,
+            },
+            "alphabetic-lower" => Condition::Class {
+                value,
+                class: Class::AlphabeticLower,
+            },
+            "alphabetic-upper" => Condition::Class {
+                value,
+                class: Class::AlphabeticUpper,
+            },
+            "positive" => Condition::Sign {
+                value,
+                sign: Sign::Positive,
+            },
+            "negative" => Condition::Sign {
+                value,
+                sign: Sign::Negative,
+            },
+            "zero" => Condition::Sign {
+                value,
+                sign: Sign::Zero,
+            },
+            _ => return None,
+        };
+        self.position += offset + 1;
+
+        if negated {
+            Some(Condition::Not(Box::new(condition)))
+        } else {
+            Some(condition)
+        }
+    }
+
+    fn at_relation(&self) -> bool {
+        self.at_relation_at(0)
+    }
+
+    fn at_relation_at(&self, offset: usize) -> bool {
+        match self.peek_at(offset) {
+            Some("is" | "not") => true,
+            Some(word) => is_relational_operator(word),
+            None => false,
+        }
+    }
+
+    // [is] [not] greater [than] [or equal [to]] | less ... | equal [to] | > | < | = | >= | <=
+    fn parse_relation(&mut self) -> Relation {
+        self.skip("is");
+        let negated = self.skip("not");
+
+        let relation = match self.next() {
+            Some(">") => Relation::GreaterThan,
+            Some("<") => Relation::LessThan,
+            Some("=") => Relation::EqualTo,
+            Some(">=") => Relation::GreaterOrEqual,
+            Some("<=") => Relation::LessOrEqual,
+            Some("equal") => {
+                self.skip("to");
+                Relation::EqualTo
+            }
+            Some(word @ ("greater" | "less")) => {
+                self.skip("than");
+                let or_equal = self.peek() == Some("or") && self.peek_at(1) == Some("equal");
+                if or_equal {
+                    self.position += 2;
+                    self.skip("to");
+                }
+
+                match (word, or_equal) {
+                    ("greater", false) => Relation::GreaterThan,
+                    ("greater", true) => Relation::GreaterOrEqual,
+                    ("less", false) => Relation::LessThan,
+                    _ => Relation::LessOrEqual,
+                }
+            }
+            word => panic!("expected a relational operator, found {word:?}"),
+        };
+
+        if negated {
+            relation.negate()
+        } else {
+            relation
+        }
+    }
+
+    fn parse_operand(&mut self) -> Value {
+        match self.next() {
+            Some(word) => resolve_value(self.look_up, word),
+            None => panic!("missing operand in condition {}", self.words.join(" ")),
+        }
+    }
+}
+
+// recursive descent over the words of an arithmetic expression. the unary signs bind tightest,
+// then exponentiation, then multiplication and division, then addition and subtraction, and
+// operators of the same precedence go from left to right
+struct ExpressionParser<'b> {
+    look_up: &'b [Data],
+    words: Vec<&'b str>,
+    position: usize,
+}
+
+impl<'b> ExpressionParser<'b> {
+    fn new(look_up: &'b [Data], operands: &[&'b str]) -> Self {
+        Self {
+            look_up,
+            words: split_parentheses(operands),
+            position: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<&'b str> {
+        self.words.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<&'b str> {
+        let word = self.peek();
+        self.position += 1;
+        word
+    }
+
+    fn parse(mut self) -> Expression {
+        let expression = self.parse_sum();
+        if let Some(word) = self.peek() {
+            panic!("unexpected {word} in expression {}", self.words.join(" "));
+        }
+
+        expression
+    }
+
+    fn parse_sum(&mut self) -> Expression {
+        let mut expression = self.parse_product();
+        loop {
+            let operator = match self.peek() {
+                Some("+") => Operator::Add,
+                Some("-") => Operator::Subtract,
+                _ => return expression,
+            };
+            self.position += 1;
+            expression = binary(expression, operator, self.parse_product());
+        }
+    }
+
+    fn parse_product(&mut self) -> Expression {
+        let mut expression = self.parse_power();
+        loop {
+            let operator = match self.peek() {
+                Some("*") => Operator::Multiply,
+                Some("/") => Operator::Divide,
+                _ => return expression,
+            };
+            self.position += 1;
+            expression = binary(expression, operator, self.parse_power());
+        }
+    }
+
+    fn parse_power(&mut self) -> Expression {
+        let mut expression = self.parse_unary();
+        while self.peek() == Some("**") {
+            self.position += 1;
+            expression = binary(expression, Operator::Power, self.parse_unary());
+        }
+
+        expression
+    }
+
+    fn parse_unary(&mut self) -> Expression {
+        match self.peek() {
+            Some("-") => {
+                self.position += 1;
+                Expression::Negate(Box::new(self.parse_unary()))
+            }
+            Some("+") => {
+                self.position += 1;
+                self.parse_unary()
+            }
+            _ => self.parse_primary(),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Expression {
+        let Some(word) = self.next() else {
+            panic!("missing operand in expression {}", self.words.join(" "));
+        };
+
+        if word == "(" {
+            let expression = self.parse_sum();
+            if self.next() != Some(")") {
+                panic!("missing ) in expression {}", self.words.join(" "));
+            }
+            return expression;
+        }
+
+        if let Some((value, scale)) = parse_numeric_literal(word) {
+            return Expression::Literal { value, scale };
+        }
+
+        // a minus sign written up against an identifier
+        if let Some(name) = word.strip_prefix('-') {
+            let operand = Expression::Value(resolve_value(self.look_up, name));
+            return Expression::Negate(Box::new(operand));
+        }
+
+        Expression::Value(resolve_value(self.look_up, word))
+    }
+}
+
+fn binary(left: Expression, operator: Operator, right: Expression) -> Expression {
+    Expression::Binary {
+        left: Box::new(left),
+        operator,
+        right: Box::new(right),
+    }
+}
+
+// [+|-]digits[.digits]
+fn parse_numeric_literal(word: &str) -> Option<(i128, u32)> {
+    let digits = word.strip_prefix(['+', '-']).unwrap_or(word);
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if whole.is_empty() && fraction.is_empty()
+        || !whole
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let value: i128 = format!("{whole}{fraction}").parse().ok()?;
+    let sign = if word.starts_with('-') { -1 } else { 1 };
+    Some((sign * value, fraction.len() as u32))
+}
+
+// the bytes as a byte string literal of the generated code. a literal is written out byte for byte,
+// so quotes and backslashes in it and the bytes of high-values are escaped rather than taken as
+// characters
+pub fn byte_string(bytes: &[u8]) -> String {
+    format!("b\"{}\"", bytes.escape_ascii())
 }
 
-impl ToString for Condition {
-    fn to_string(&self) -> String {
-        match self {
-            Condition::EqualTo => String::from("="),
-            Condition::GreaterThan => String::from(">"),
-            Condition::LessThan => String::from("<"),
-        }
+// the literal as it was written, with its decimal point put back
+pub fn format_decimal(value: i128, scale: u32) -> String {
+    let digits = format!(
+        "{:0width$}",
+        value.unsigned_abs(),
+        width = scale as usize + 1
+    );
+    let (whole, fraction) = digits.split_at(digits.len() - scale as usize);
+    let sign = if value < 0 { "-" } else { "" };
+    if fraction.is_empty() {
+        format!("{sign}{whole}")
+    } else {
+        format!("{sign}{whole}.{fraction}")
     }
 }
 
-impl Derive for Condition {
-    fn derive(val: &str) -> Self {
-        match val {
-            "greater" => Condition::GreaterThan,
-            "less" => Condition::LessThan,
-            "equal" => Condition::EqualTo,
-            _ => unreachable!(),
-        }
-    }
+fn parse_expression(look_up: &[Data], operands: &[&str]) -> Expression {
+    ExpressionParser::new(look_up, operands).parse()
 }
 
-#[derive(Debug, Clone)]
-pub enum IdentifierType {
-    Numeric(u32),
-    Alphabetic,
-    Alphanumeric(u32),
-    ImplicitDecimal,
-    Sign,
-    AssumedDecimal,
+fn is_relational_operator(word: &str) -> bool {
+    matches!(
+        word,
+        ">" | "<" | "=" | ">=" | "<=" | "greater" | "less" | "equal"
+    )
 }
 
-impl IdentifierType {
-    fn parse_type(string: Arc<str>) -> Self {
-        let chars: Vec<char> = string.chars().collect();
-        let num = if let Some(c) = chars.get(1) {
-            if *c == '(' {
-                let mut num = String::new();
-                let mut index = 2;
-                loop {
-                    let digit = chars[index];
-                    if digit == ')' {
-                        break;
-                    } else {
-                        num.push(digit);
-                        index += 1;
-                        if index > 100 {
-                            panic!("forgot closing )");
-                        }
-                    }
-                }
-                let amount: u32 = num
-                    .parse()
-                    .expect("defined var has () without a number inside");
+// parentheses are not separated from the words next to them by whitespace, so `(a` and `b)` are
+// split up here. a `(` inside of a word is left alone
+fn split_parentheses<'b>(operands: &[&'b str]) -> Vec<&'b str> {
+    let mut words = vec![];
+    for operand in operands {
+        let mut word = *operand;
+        while let Some(rest) = word.strip_prefix('(') {
+            words.push("(");
+            word = rest;
+        }
 
-                amount
-            } else {
-                // hack, need to parse for the period that ends the line. right now i am just
-                // counting it and then subtracting 1
-                chars.len() as u32 - 1
-            }
-        } else {
-            1
-        };
+        let mut closing = 0;
+        while word.ends_with(')') && word.matches(')').count() > word.matches('(').count() {
+            word = &word[..word.len() - 1];
+            closing += 1;
+        }
 
-        if chars[0] == '9' {
-            IdentifierType::Numeric(num)
-        } else if chars[0] == 'x' {
-            IdentifierType::Alphanumeric(num)
-        } else {
-            unimplemented!()
+        if !word.is_empty() {
+            words.push(word);
         }
+        words.extend(std::iter::repeat_n(")", closing));
     }
+
+    words
 }
 
-#[derive(Debug)]
-pub struct Ident {
-    pub name: Arc<str>,
-    pub kind: IdentifierType,
+fn parse_condition(look_up: &[Data], operands: &[&str]) -> Condition {
+    ConditionParser::new(look_up, operands).parse()
 }
 
-impl Ident {
-    pub fn new(name: &str, kind: IdentifierType) -> Self {
-        Self {
-            name: Arc::from(name),
-            kind,
+// 88 name value[s] [is|are] literal [thru|through literal] ...
+fn parse_condition_name(words: &[&str]) -> ConditionName {
+    let name = Arc::from(words[1]);
+    let literals: Vec<&str> = words[2..]
+        .iter()
+        .filter(|w| !matches!(**w, "value" | "values" | "is" | "are"))
+        .map(|w| w.trim_end_matches(','))
+        .collect();
+
+    // all is followed by the literal it repeats
+    let mut joined: Vec<String> = vec![];
+    let mut literals = literals.into_iter();
+    while let Some(literal) = literals.next() {
+        match literal {
+            "all" => joined.push(format!("all {}", literals.next().unwrap_or_default())),
+            literal => joined.push(String::from(literal)),
+        }
+    }
+
+    let mut values = vec![];
+    let mut index = 0;
+    while index < joined.len() {
+        let value = Value::derive(&joined[index]);
+        if matches!(
+            joined.get(index + 1).map(|w| w.as_str()),
+            Some("thru" | "through")
+        ) {
+            let high = joined
+                .get(index + 2)
+                .expect("thru without an upper bound in condition name");
+            values.push(ConditionValue::Range(value, Value::derive(high)));
+            index += 3;
+        } else {
+            values.push(ConditionValue::Single(value));
+            index += 1;
         }
     }
+
+    if values.is_empty() {
+        panic!("condition name {} has no values", name);
+    }
+
+    ConditionName { name, values }
 }
 
-#[derive(Debug)]
-pub enum Value {
-    Number(i32),
-    Identifier(Ident),
-    String(Arc<str>),
+// level 77 entries stand on their own just like level 01 ones
+fn nesting(level: i32) -> i32 {
+    if level == 77 {
+        1
+    } else {
+        level
+    }
 }
 
-impl Display for Value {
-    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
-        match self {
-            Value::Number(i) => write!(f, "{}", i),
-            Value::Identifier(ident) => write!(f, "{}", ident.name),
-            Value::String(s) => write!(f, "\"{}\"", s),
+// the open entries at level or deeper are complete, and are added to the group they are in
+fn close_groups(open: &mut Vec<Data>, variables: &mut Vec<Data>, level: i32) {
+    while open.last().is_some_and(|data| nesting(data.level) >= level) {
+        let data = open.pop().unwrap();
+        if let (DataType::Group, true) = (&data.data_type, data.children.is_empty()) {
+            panic!("{} has neither a picture nor any items in it", data.name);
         }
+        let siblings = match open.last_mut() {
+            Some(parent) => &mut parent.children,
+            None => &mut *variables,
+        };
+        check_redefines(siblings, &data);
+        check_value(&data);
+        siblings.push(data);
     }
 }
 
-impl Derive for Value {
-    fn derive(val: &str) -> Self {
-        let try_parse = val.parse::<i32>();
-        if let Ok(value) = try_parse {
-            return Self::Number(value);
-        }
+// an item can only redefine the item right before it on the same level, or one that the items in
+// between redefine as well, and it cannot take more bytes than that item
+fn check_redefines(siblings: &[Data], data: &Data) {
+    let Some(redefined) = &data.redefines else {
+        return;
+    };
+    let original = siblings.iter().rposition(|s| s.redefines.is_none());
+    let Some(original) = original.filter(|i| siblings[*i..].iter().any(|s| s.name == *redefined))
+    else {
+        panic!(
+            "{} can only redefine the item right before it, not {}",
+            data.name, redefined
+        );
+    };
+    if data.size() > siblings[original].size() {
+        panic!(
+            "{} takes more bytes than {}, which it redefines",
+            data.name, siblings[original].name
+        );
+    }
+}
 
-        if val.starts_with('\"') && val.ends_with('\"') {
-            let actual_string = val.strip_prefix('\"').unwrap().strip_suffix('\"').unwrap();
-            return Self::String(Arc::from(actual_string));
+// 66 name renames item [thru|through item]. the items are looked for in the record, and a range
+// has to run forward through it
+fn parse_renames(words: &[&str], record: &Data) -> Data {
+    let (name, from, thru) = match words {
+        [_, name, "renames", from] => (name, from, None),
+        [_, name, "renames", from, "thru" | "through", thru] => (name, from, Some(thru)),
+        _ => panic!("unsupported renames entry {}", words.join(" ")),
+    };
+
+    let mut layout = vec![];
+    lay_out(&record.children, 0, false, &mut layout);
+    let find = |wanted: &str| {
+        let mut matches = layout.iter().filter(|(data, ..)| &*data.name == wanted);
+        match (matches.next(), matches.next()) {
+            (Some((_, _, true)), _) => panic!("{name} cannot rename {wanted}, which is in a table"),
+            (Some(found), None) => *found,
+            (Some(_), Some(_)) => panic!("{wanted} is ambiguous in {}", record.name),
+            (None, _) => panic!("{name} renames {wanted}, which is not in {}", record.name),
         }
+    };
 
-        Value::Identifier(Ident::new(val, IdentifierType::Numeric(0)))
+    let (first, offset, _) = find(from);
+    // a group renamed on its own is a group without any items to look at, like a range is
+    let (data_type, usage, length) = match thru {
+        None => (first.data_type.clone(), first.usage, first.size()),
+        Some(thru) => {
+            let (last, start, _) = find(thru);
+            let end = start + last.size();
+            if end <= offset {
+                panic!("{name} renames {from} through {thru}, which comes before it");
+            }
+            (DataType::Group, Usage::Display, end - offset)
+        }
+    };
+    Data {
+        level: 66,
+        name: Arc::from(*name),
+        data_type,
+        usage,
+        value: None,
+        justified: false,
+        occurs: None,
+        redefines: None,
+        renames: Some(Renames { offset, length }),
+        conditions: vec![],
+        children: vec![],
     }
 }
 
-#[derive(Debug)]
-pub struct Infix {
-    pub left: Value,
-    pub right: Ident,
+// every item in items with the offset it starts at, and whether it is in a table. an item that
+// redefines another starts where that one does, and only the first entry of a table is laid out
+fn lay_out<'d>(
+    items: &'d [Data],
+    start: u32,
+    in_table: bool,
+    layout: &mut Vec<(&'d Data, u32, bool)>,
+) {
+    let mut offset = start;
+    let mut original = start;
+    for data in items.iter().filter(|data| data.renames.is_none()) {
+        let at = if data.redefines.is_some() {
+            original
+        } else {
+            original = offset;
+            offset += data.size();
+            original
+        };
+        let in_table = in_table || data.occurs.is_some();
+        layout.push((data, at, in_table));
+        lay_out(&data.children, at, in_table, layout);
+    }
 }
 
-#[derive(Debug)]
-pub enum Instruction {
-    Move(Infix),
-    Add(Infix),
-    Multiply(Infix),
-    Print(Vec<Value>),
-    Repeat {
-        left: Value,
-        condition: Condition,
-        right: Value,
-        insts: Vec<Instruction>,
-    },
+fn is_data_clause(word: &str) -> bool {
+    matches!(
+        word,
+        "pic"
+            | "picture"
+            | "usage"
+            | "occurs"
+            | "redefines"
+            | "value"
+            | "justified"
+            | "just"
+            | "sync"
+            | "synchronized"
+            | "sign"
+            | "leading"
+            | "trailing"
+    ) || Usage::parse(word).is_some()
 }
 
-pub struct Parser<'a> {
-    contents: &'a str,
-    lines: VecDeque<&'a str>,
-    look_up: Vec<Data>,
+// leading | trailing [separate [character]]. a sign that is not separate is overpunched on the
+// first or last digit, and trailing is where display keeps it anyway
+fn parse_sign<'w>(position: &str, clauses: &mut Peekable<impl Iterator<Item = &'w str>>) -> Usage {
+    let separate = clauses.next_if_eq(&"separate").is_some();
+    if separate {
+        clauses.next_if_eq(&"character");
+    }
+    match (position, separate) {
+        ("leading", false) => Usage::LeadingSign,
+        ("leading", true) => Usage::LeadingSeparate,
+        (_, true) => Usage::TrailingSeparate,
+        (_, false) => Usage::Display,
+    }
 }
 
-impl<'a> Parser<'a> {
-    pub fn new(contents: &'a str) -> Self {
-        Self {
-            contents,
-            lines: VecDeque::new(),
-            look_up: vec![],
+// a numeric item can only start out as a number that fits its picture, or as zero. anything else
+// starts out as text, which has to fit it as well
+fn check_value(data: &Data) {
+    let Some(value) = &data.value else {
+        return;
+    };
+    let fits = match (value, &data.data_type) {
+        (Value::Figurative(Figurative::Zero), _) => true,
+        (_, DataType::Picture(IdentifierType::Numeric(picture))) => {
+            let (digits, scale) = match value {
+                Value::Number(number) => (*number as i128, 0),
+                Value::Decimal { value, scale } => (*value, *scale as i32),
+                _ => panic!(
+                    "{} is numeric, so it cannot start out as {}",
+                    data.name, value
+                ),
+            };
+            // the literal in units of the last digit of the picture, which has to be whole
+            let shift = picture.scale - scale;
+            let units = if shift >= 0 {
+                Some(digits * 10i128.pow(shift as u32))
+            } else {
+                let divisor = 10i128.pow(shift.unsigned_abs());
+                (digits % divisor == 0).then(|| digits / divisor)
+            };
+            units.is_some_and(|units| {
+                units.unsigned_abs() < 10u128.pow(picture.digits) && (picture.signed || units >= 0)
+            })
         }
+        (Value::String(text), _) => text.len() as u32 <= data.size(),
+        (Value::Figurative(_), _) => true,
+        _ => panic!("{} can only start out as text, not {}", data.name, value),
+    };
+    if !fits {
+        panic!("{} does not fit {}", value, data.name);
     }
+}
 
-    pub fn parse(mut self) -> (Vec<Data>, Vec<Instruction>) {
-        let pro_split: Vec<&str> = self.contents.split("procedure division.").collect();
-        let data_split: Vec<&str> = pro_split[0].split("data division.").collect();
+// occurs [min to] max [times] [depending [on] name] [ascending | descending [key] [is] name...]...
+// [indexed [by] name...]
+fn parse_occurs<'w>(clauses: &mut Peekable<impl Iterator<Item = &'w str>>) -> Occurs {
+    let count = |word: Option<&str>| -> u32 {
+        let word = word.expect("occurs clause without a count");
+        word.parse()
+            .unwrap_or_else(|_| panic!("occurs {word} is not a count"))
+    };
+    let first = count(clauses.next());
+    let (min, max) = if clauses.next_if_eq(&"to").is_some() {
+        (first, count(clauses.next()))
+    } else {
+        (first, first)
+    };
+    clauses.next_if_eq(&"times");
+    if max == 0 || min > max {
+        panic!("occurs {min} to {max} is not a valid number of entries");
+    }
 
-        let procedure = pro_split[1].trim_start();
-        let data = data_split[1].trim_start();
-        let variables = self.parse_data(data);
-        self.look_up = variables;
-        let instructions = self.parse_procedure(procedure);
+    let mut occurs = Occurs {
+        min,
+        max,
+        depending: None,
+        keys: vec![],
+        indexes: vec![],
+    };
 
-        (self.look_up, instructions)
+    // the names in a key or indexed by phrase run up to the next phrase or clause
+    let is_phrase =
+        |word: &str| matches!(word, "depending" | "ascending" | "descending" | "indexed");
+    let is_name = |word: &&str| !is_data_clause(word) && !is_phrase(word);
+    while let Some(word) = clauses.next_if(|w| is_phrase(w)) {
+        match word {
+            "depending" => {
+                clauses.next_if_eq(&"on");
+                let name = clauses.next().expect("depending on without an item");
+                occurs.depending = Some(Arc::from(name));
+            }
+            "indexed" => {
+                clauses.next_if_eq(&"by");
+                occurs
+                    .indexes
+                    .extend(std::iter::from_fn(|| clauses.next_if(is_name)).map(Arc::from));
+            }
+            _ => {
+                clauses.next_if_eq(&"key");
+                clauses.next_if_eq(&"is");
+                let ascending = word == "ascending";
+                occurs
+                    .keys
+                    .extend(
+                        std::iter::from_fn(|| clauses.next_if(is_name)).map(|name| Key {
+                            name: Arc::from(name),
+                            ascending,
+                        }),
+                    );
+            }
+        }
     }
 
-    fn parse_data(&self, data_segment: &'a str) -> Vec<Data> {
-        let working_storage_split: Vec<&str> =
-            data_segment.split("working-storage section.").collect();
+    if occurs.depending.is_none() && occurs.min != occurs.max {
+        panic!(
+            "occurs {} to {} without depending on",
+            occurs.min, occurs.max
+        );
+    }
+    occurs
+}
 
-        let working_storage_section = working_storage_split[1];
-        let working_storage_data: Vec<&str> = working_storage_section.lines().collect();
-        let ws_data = self.parse_working_storage(working_storage_data);
+// the categories of elementary items that initialize tells apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Category {
+    Alphabetic,
+    Alphanumeric,
+    AlphanumericEdited,
+    Numeric,
+    NumericEdited,
+}
 
-        ws_data
-    }
+impl Category {
+    const ALL: [Category; 5] = [
+        Category::Alphabetic,
+        Category::Alphanumeric,
+        Category::AlphanumericEdited,
+        Category::Numeric,
+        Category::NumericEdited,
+    ];
 
-    fn parse_working_storage(&self, working_storage_lines: Vec<&str>) -> Vec<Data> {
-        working_storage_lines
-            .iter()
-            .filter(|l| l.trim() != "")
-            .map(|line| {
-                let trimmed = line.trim_start();
-                let variable = self.parse_variable(trimmed);
+    fn parse(word: &str) -> Option<Self> {
+        match word {
+            "alphabetic" => Some(Category::Alphabetic),
+            "alphanumeric" => Some(Category::Alphanumeric),
+            "alphanumeric-edited" => Some(Category::AlphanumericEdited),
+            "numeric" => Some(Category::Numeric),
+            "numeric-edited" => Some(Category::NumericEdited),
+            _ => None,
+        }
+    }
 
-                return variable;
-            })
-            .collect()
+    fn of(kind: &IdentifierType) -> Option<Self> {
+        match kind {
+            IdentifierType::Numeric(_) => Some(Category::Numeric),
+            IdentifierType::NumericEdited(_) => Some(Category::NumericEdited),
+            IdentifierType::Alphabetic(_) => Some(Category::Alphabetic),
+            IdentifierType::Alphanumeric(_) => Some(Category::Alphanumeric),
+            IdentifierType::AlphanumericEdited(_) => Some(Category::AlphanumericEdited),
+            IdentifierType::Group => None,
+        }
     }
+}
 
-    fn parse_variable(&self, line: &str) -> Data {
-        println!("{}", line);
-        let words = get_words(line);
+// the phrases of an initialize statement: whether fillers are set, the categories that take their
+// value clauses, what replaces each category and whether the rest fall back on the default
+#[derive(Debug, Default)]
+struct Initializing {
+    filler: bool,
+    values: Vec<Category>,
+    replacing: Vec<(Category, Value)>,
+    default: bool,
+}
 
-        let level: i32 = words[0].parse().expect("cannot convert level str into i32");
-        let name = words[1].clone();
-        let v_type = words[2].clone();
-        let variable_definition = words[3].clone();
-        let variable_identifier_type = IdentifierType::parse_type(variable_definition);
-        let var_type = match &*v_type {
-            "pic" => DataType::Picture(variable_identifier_type),
-            _ => unimplemented!(),
+impl Initializing {
+    // what an elementary item is set to, if anything
+    fn value_for(&self, data: &Data) -> Option<Value> {
+        let category = Category::of(&data.kind())?;
+        if let Some(value) = data
+            .value
+            .as_ref()
+            .filter(|_| self.values.contains(&category))
+        {
+            return Some(value.clone());
+        }
+        if let Some((_, value)) = self.replacing.iter().find(|(c, _)| *c == category) {
+            return Some(value.clone());
+        }
+        let default = match category {
+            Category::Numeric | Category::NumericEdited => Figurative::Zero,
+            _ => Figurative::Space,
         };
+        self.default.then_some(Value::Figurative(default))
+    }
+}
 
-        Data {
-            level,
+// adds a move for every elementary item from data down that initialize sets. the item keeps the
+// subscripts the statement gave the identifier it is under, and goes through every entry of the
+// tables between the two. path holds the names of the fields the item is in, where a filler is
+// named by how many fillers come before it in its group, the way its field is
+fn initialize_item<'d>(
+    look_up: &[Data],
+    initializing: &Initializing,
+    ancestors: &mut Vec<&'d Data>,
+    path: &mut Vec<Arc<str>>,
+    (name, data): (Arc<str>, &'d Data),
+    identifier: &Ident,
+    moves: &mut Vec<Infix>,
+) {
+    if let DataType::Picture(_) = data.data_type {
+        let Some(value) = initializing.value_for(data) else {
+            return;
+        };
+        let mut right = Ident {
             name,
-            data_type: var_type,
+            path: path.clone(),
+            subscripts: identifier.subscripts.clone(),
+            ..ident_of(ancestors, data)
+        };
+        let chain = ancestors.iter().copied().chain([data]).enumerate();
+        for (position, item) in chain.skip(identifier.path.len()) {
+            if let Some(occurs) = &item.occurs {
+                if identifier.subscripts.iter().all(|s| s.position != position) {
+                    right.subscripts.push(Subscript {
+                        position,
+                        index: Index::Every,
+                        count: count_of(look_up, occurs),
+                    });
+                }
+            }
         }
+        moves.push(Infix { left: value, right });
+        return;
     }
 
-    fn parse_procedure(&mut self, procedure: &'a str) -> Vec<Instruction> {
-        let mut instructions = vec![];
-        self.lines = procedure.lines().collect();
-        loop {
-            let line = if let Some(l) = self.lines.pop_front() {
-                l
+    ancestors.push(data);
+    path.push(name);
+    let mut fillers = 0;
+    for child in &data.children {
+        let name = if child.is_filler() {
+            fillers += 1;
+            Arc::from(format!("filler-{}", fillers))
+        } else {
+            child.name.clone()
+        };
+        if !child.is_view() && (initializing.filler || !child.is_filler()) {
+            let item = (name, child);
+            initialize_item(
+                look_up,
+                initializing,
+                ancestors,
+                path,
+                item,
+                identifier,
+                moves,
+            );
+        }
+    }
+    path.pop();
+    ancestors.pop();
+}
+
+// an item under a group as corresponding sees it, named by its own name and those of the groups
+// between it and that group
+struct Subordinate<'d> {
+    names: Vec<Arc<str>>,
+    ident: Ident,
+    data: &'d Data,
+}
+
+// the pairs of items under two groups that have the same names from the groups down. a move pairs
+// them when one of the two is elementary, and arithmetic when both are numeric. the elementary
+// items of either group that end up in no pair, and are not under an item in one, are reported
+fn corresponding(
+    look_up: &[Data],
+    statement: &str,
+    sending: &str,
+    receiving: &str,
+    numeric: bool,
+) -> Vec<(Ident, Ident)> {
+    let [sending_items, receiving_items] = [sending, receiving].map(|word| {
+        let (mut ancestors, data, ident) =
+            find_item(look_up, word).unwrap_or_else(|| panic!("{word} is not defined"));
+        if !matches!(data.data_type, DataType::Group) {
+            panic!("{word} is not a group, so nothing can correspond to its items");
+        }
+        let mut items = vec![];
+        subordinates(&mut ancestors, data, &ident, &mut items);
+        items
+    });
+
+    let elementary = |data: &Data| matches!(data.data_type, DataType::Picture(_));
+    let is_numeric = |data: &Data| matches!(data.kind(), IdentifierType::Numeric(_));
+    let pairs: Vec<(&Subordinate, &Subordinate)> = sending_items
+        .iter()
+        .filter_map(|left| {
+            let right = receiving_items.iter().find(|r| r.names == left.names)?;
+            let pair = if numeric {
+                is_numeric(left.data) && is_numeric(right.data)
             } else {
-                break;
+                elementary(left.data) || elementary(right.data)
             };
+            pair.then_some((left, right))
+        })
+        .collect();
 
-            // let words: Vec<&str> = line.split_whitespace().collect();
-            let words: Vec<Arc<str>> = walk_line(line);
-            let str_words: Vec<&str> = words.iter().map(|w| &**w).collect();
-            let instruction = self.generate_instruction(str_words);
-            instructions.push(instruction);
+    let mut left_out = vec![];
+    for (group, items, side) in [
+        (sending, &sending_items, 0),
+        (receiving, &receiving_items, 1),
+    ] {
+        for item in items.iter() {
+            let candidate = elementary(item.data) && (!numeric || is_numeric(item.data));
+            let paired = pairs.iter().any(|pair| {
+                let names = if side == 0 {
+                    &pair.0.names
+                } else {
+                    &pair.1.names
+                };
+                item.names.starts_with(names)
+            });
+            if candidate && !paired {
+                let names: Vec<&str> = item.names.iter().rev().map(|n| &**n).collect();
+                left_out.push(format!("{} of {}", names.join(" of "), group));
+            }
         }
-
-        instructions
+    }
+    if !left_out.is_empty() {
+        eprintln!(
+            "warning: {} corresponding {} and {} leaves out {}, which have nothing of the same \
+             name to correspond to",
+            statement,
+            sending,
+            receiving,
+            left_out.join(", ")
+        );
     }
 
-    fn generate_instruction(&mut self, words: Vec<&str>) -> Instruction {
-        let instruction = words[0];
-        let tail = words.tail();
+    pairs
+        .into_iter()
+        .map(|(left, right)| (left.ident.clone(), right.ident.clone()))
+        .collect()
+}
 
-        match instruction {
-            "move" | "add" | "multiply" => self.generate_infix_instruction(instruction, tail),
-            "display" => self.generate_print(tail),
-            "perform" => self.generate_perform(tail),
-            _ => panic!("unimplemented instruction {instruction}"),
+// adds the items under group that corresponding can pair up. fillers, items that redefine or
+// rename others and tables are left out, along with everything under them
+fn subordinates<'d>(
+    ancestors: &mut Vec<&'d Data>,
+    group: &'d Data,
+    identifier: &Ident,
+    items: &mut Vec<Subordinate<'d>>,
+) {
+    ancestors.push(group);
+    for data in &group.children {
+        if data.is_filler() || data.is_view() || data.occurs.is_some() {
+            continue;
         }
+        let names = ancestors[identifier.path.len() + 1..]
+            .iter()
+            .map(|a| a.name.clone())
+            .chain([data.name.clone()])
+            .collect();
+        let ident = Ident {
+            subscripts: identifier.subscripts.clone(),
+            ..ident_of(ancestors, data)
+        };
+        items.push(Subordinate { names, ident, data });
+        subordinates(ancestors, data, identifier, items);
     }
+    ancestors.pop();
+}
 
-    fn generate_print(&self, operands: &[&str]) -> Instruction {
-        let values: Vec<Value> = operands.iter().map(|o| Value::derive(o)).collect();
+// calls visit with every item and the groups it is in, outermost first
+pub fn walk_data<'d>(
+    items: &'d [Data],
+    ancestors: &mut Vec<&'d Data>,
+    visit: &mut impl FnMut(&[&'d Data], &'d Data),
+) {
+    for data in items {
+        visit(ancestors, data);
+        ancestors.push(data);
+        walk_data(&data.children, ancestors, visit);
+        ancestors.pop();
+    }
+}
 
-        Instruction::Print(values)
+// the groups in a qualified name have to appear in the path in that order, innermost first, but
+// need not be the ones right above the item
+fn is_qualified_by(ancestors: &[&Data], qualifiers: &[&str]) -> bool {
+    let mut groups = ancestors.iter().rev();
+    qualifiers
+        .iter()
+        .all(|qualifier| groups.any(|group| &*group.name == *qualifier))
+}
+
+// name [of group]... [(subscript...)], which has to be enough to tell the item apart from any
+// other of that name
+fn find_data(look_up: &[Data], word: &str) -> Option<Ident> {
+    find_item(look_up, word).map(|(_, _, ident)| ident)
+}
+
+// the item a name refers to along with the groups it is in, for when its subordinates matter
+fn find_item<'d>(look_up: &'d [Data], word: &str) -> Option<(Vec<&'d Data>, &'d Data, Ident)> {
+    let (word, subscripts) = split_subscripts(word);
+    let mut names = word.split(" of ");
+    let name = names.next()?;
+    let qualifiers: Vec<&str> = names.collect();
+
+    let mut found = vec![];
+    walk_data(look_up, &mut vec![], &mut |ancestors, data| {
+        if &*data.name == name && is_qualified_by(ancestors, &qualifiers) {
+            found.push((ancestors.to_vec(), data));
+        }
+    });
+
+    if found.len() > 1 {
+        panic!("{word} is ambiguous, qualify it with a group it is in");
+    }
+    let (ancestors, data) = found.pop()?;
+    let ident = Ident {
+        subscripts: resolve_subscripts(look_up, &word, &ancestors, data, &subscripts),
+        ..ident_of(&ancestors, data)
+    };
+    Some((ancestors, data, ident))
+}
+
+// the item, without any subscripts, as it is reached through the groups it is in
+fn ident_of(ancestors: &[&Data], data: &Data) -> Ident {
+    let chain = ancestors.iter().copied().chain([data]);
+    Ident {
+        name: data.name.clone(),
+        kind: data.kind(),
+        path: ancestors.iter().map(|a| a.name.clone()).collect(),
+        subscripts: vec![],
+        views: chain
+            .enumerate()
+            .filter(|(_, d)| d.is_view())
+            .map(|(position, _)| position)
+            .collect(),
+        justified: data.justified,
+        variable: data.depending_table().is_some(),
     }
+}
+
+// the name of the method that works out how many bytes of a variable item are in use
+pub fn length_accessor(path: &[Arc<str>], name: &str) -> String {
+    let names: Vec<&str> = path.iter().map(|n| &**n).chain([name]).collect();
+    format!("{}_length", names.join("_").replace('-', "_"))
+}
 
-    fn generate_infix_instruction(&self, inst: &str, operands: &[&str]) -> Instruction {
-        let src = operands[0];
-        let dest = operands[2];
+// takes the subscripts off of a name, which come after its qualifiers but may also be written
+// right after the name itself, so item(i) of table reads the same as item of table(i)
+fn split_subscripts(word: &str) -> (String, Vec<&str>) {
+    let Some(open) = word.find('(') else {
+        return (String::from(word), vec![]);
+    };
+    let close = word
+        .rfind(')')
+        .unwrap_or_else(|| panic!("missing ) in {word}"));
+    let name = format!("{}{}", word[..open].trim_end(), &word[close + 1..]);
+    let subscripts = word[open + 1..close]
+        .split([',', ' '])
+        .filter(|s| !s.is_empty())
+        .collect();
+    (name, subscripts)
+}
 
-        let DataType::Picture(i_type) = self 
-            .look_up
+// every table the item is in, outermost first, takes one of the subscripts in order. a table can
+// go without them when nothing around it is a table, which refers to all of its entries at once
+fn resolve_subscripts(
+    look_up: &[Data],
+    word: &str,
+    ancestors: &[&Data],
+    data: &Data,
+    words: &[&str],
+) -> Vec<Subscript> {
+    let tables: Vec<(usize, &Occurs)> = ancestors
+        .iter()
+        .copied()
+        .chain([data])
+        .enumerate()
+        .filter_map(|(position, d)| d.occurs.as_ref().map(|occurs| (position, occurs)))
+        .collect();
+    if words.is_empty()
+        && tables
             .iter()
-            .find(|v| &*v.name == dest)
-            .unwrap()
-            .data_type
-            .clone()
-        else {
-            unimplemented!()
-        };
+            .all(|(position, _)| *position == ancestors.len())
+    {
+        return vec![];
+    }
 
-        let infix = Infix {
-            left: Value::derive(src),
-            right: Ident::new(dest, i_type),
+    // a relative subscript is an index or item with a number added or taken off of it
+    let mut subscripts = vec![];
+    let mut words = words.iter().peekable();
+    while let Some(word) = words.next() {
+        let value = match resolve_value(look_up, word) {
+            value @ Value::Number(1..) => value,
+            value @ Value::Identifier(Ident {
+                kind: IdentifierType::Numeric(_),
+                ..
+            }) => value,
+            value => panic!("{value} cannot be a subscript"),
+        };
+        let offset = match words.next_if(|w| matches!(**w, "+" | "-")) {
+            Some(sign) => {
+                let number: i32 = words
+                    .next()
+                    .and_then(|w| w.parse().ok())
+                    .unwrap_or_else(|| panic!("{sign} in a subscript without a number after it"));
+                if *sign == "-" {
+                    -number
+                } else {
+                    number
+                }
+            }
+            None => 0,
         };
+        subscripts.push((value, offset));
+    }
 
-        match inst {
-            "move" => Instruction::Move(infix),
-            "add" => Instruction::Add(infix),
-            "multiply" => Instruction::Multiply(infix),
-            _ => unreachable!(),
-        }
+    if subscripts.len() != tables.len() {
+        panic!(
+            "{word} needs {} subscripts, one for every table it is in",
+            tables.len()
+        );
     }
+    tables
+        .into_iter()
+        .zip(subscripts)
+        .map(|((position, occurs), (value, offset))| Subscript {
+            position,
+            index: Index::Value { value, offset },
+            count: count_of(look_up, occurs),
+        })
+        .collect()
+}
 
-    fn generate_perform(&mut self, operands: &[&str]) -> Instruction {
-        let operation = operands[0];
-        let tail = operands.tail();
+pub fn count_of(look_up: &[Data], occurs: &Occurs) -> Count {
+    match &occurs.depending {
+        Some(depending) => Count::Depending(Box::new(
+            find_data(look_up, depending)
+                .unwrap_or_else(|| panic!("depending on {depending} is not defined")),
+        )),
+        None => Count::Fixed(occurs.max),
+    }
+}
 
-        match operation {
-            "until" => self.generate_repeat(tail),
-            _ => unimplemented!(),
+// a condition name of an item in a table takes the subscripts of its parent, which say what entry
+// it is about
+fn find_condition_name(look_up: &[Data], word: &str) -> Option<Condition> {
+    let (word, subscripts) = split_subscripts(word);
+    let mut names = word.split(" of ");
+    let name = names.next()?;
+    let qualifiers: Vec<&str> = names.collect();
+
+    let mut found = None;
+    walk_data(look_up, &mut vec![], &mut |ancestors, data| {
+        let mut parents = ancestors.to_vec();
+        parents.push(data);
+        let condition = data.conditions.iter().find(|c| &*c.name == name);
+        if let Some(condition) = condition.filter(|_| is_qualified_by(&parents, &qualifiers)) {
+            if subscripts.is_empty() && parents.iter().any(|d| d.occurs.is_some()) {
+                panic!("{word} needs a subscript for every table it is in");
+            }
+            found = Some(Condition::Named {
+                name: condition.name.clone(),
+                parent: Ident {
+                    subscripts: resolve_subscripts(look_up, &word, ancestors, data, &subscripts),
+                    ..ident_of(ancestors, data)
+                },
+            });
         }
+    });
+
+    found
+}
+
+// derives a value, filling in the type of an identifier from its data definition
+fn resolve_value(look_up: &[Data], word: &str) -> Value {
+    match Value::derive(word) {
+        Value::Identifier(ident) => match find_data(look_up, word) {
+            Some(ident) => Value::Identifier(ident),
+            None => Value::Identifier(ident),
+        },
+        value => value,
     }
+}
 
-    fn generate_repeat(&mut self, operands: &[&str]) -> Instruction {
-        let left = Value::derive(operands[0]);
-        let condition = Condition::derive(operands[1]);
-        let right = Value::derive(operands[3]);
+fn resolve_receiver(look_up: &[Data], word: &str) -> Ident {
+    match resolve_value(look_up, word) {
+        Value::Identifier(ident) => ident,
+        value => panic!("cannot store into literal {value}"),
+    }
+}
 
-        let mut instructions = vec![];
-        loop {
-            let line = if let Some(l) = self.lines.pop_front() {
-                l
-            } else {
-                panic!("Missing corresponding 'end-perform'");
-            };
+fn moves_to(look_up: &[Data], value: &Value, receivers: &[&str]) -> Vec<Infix> {
+    receivers
+        .iter()
+        .map(|receiver| Infix {
+            left: value.clone(),
+            right: resolve_receiver(look_up, receiver),
+        })
+        .collect()
+}
 
-            if line.contains("end-perform.") {
-                break;
-            }
+// identifier [rounded [mode [is] mode-name]]...
+fn resolve_receivers(look_up: &[Data], mut words: &[&str]) -> Vec<Receiver> {
+    if words.is_empty() {
+        panic!("arithmetic statement without a receiver");
+    }
 
-            // let words: Vec<&str> = line.split_whitespace().collect();
-            let words: Vec<Arc<str>> = walk_line(line);
-            let str_words: Vec<&str> = words.iter().map(|w| &**w).collect();
-            let instruction = self.generate_instruction(str_words);
-            instructions.push(instruction);
+    let mut receivers: Vec<Receiver> = vec![];
+    while let [word, rest @ ..] = words {
+        words = rest;
+        if *word != "rounded" {
+            receivers.push(Receiver {
+                ident: resolve_receiver(look_up, word),
+                rounding: None,
+            });
+            continue;
         }
 
-        Instruction::Repeat {
-            left,
-            condition,
-            right,
-            insts: instructions,
-        }
+        let mode = match words {
+            ["mode", "is", mode, rest @ ..] | ["mode", mode, rest @ ..] => {
+                words = rest;
+                match *mode {
+                    "away-from-zero" => Rounding::AwayFromZero,
+                    "nearest-away-from-zero" => Rounding::NearestAwayFromZero,
+                    "nearest-even" => Rounding::NearestEven,
+                    "nearest-toward-zero" => Rounding::NearestTowardZero,
+                    "prohibited" => Rounding::Prohibited,
+                    "toward-greater" => Rounding::TowardGreater,
+                    "toward-lesser" => Rounding::TowardLesser,
+                    "truncation" => Rounding::Truncation,
+                    _ => panic!("unknown rounding mode {mode}"),
+                }
+            }
+            _ => Rounding::NearestAwayFromZero,
+        };
+        receivers
+            .last_mut()
+            .expect("rounded without a receiver")
+            .rounding = Some(mode);
     }
+
+    receivers
 }
 
 fn get_words(mut line: &str) -> Vec<Arc<str>> {
@@ -368,18 +3375,180 @@ fn get_words(mut line: &str) -> Vec<Arc<str>> {
         if let Some((word, rest)) = take {
             words.push(word);
             line = rest.trim_start();
+            if line.is_empty() {
+                break;
+            }
         } else {
             words.push(line.into());
             break;
         }
     }
 
-    return words;
+    words
+}
+
+// the subscripts of a table item are made part of its name, since they may hold spaces and commas
+// (item(i, j + 1)) and may be set apart from the name (item (i)). a word that opens more
+// parentheses than it closes takes the words after it until they are closed again, leading ones
+// aside, as those group a condition or an expression
+fn join_subscripts(words: impl Iterator<Item = Arc<str>>, look_up: &[Data]) -> Vec<Arc<str>> {
+    let depth = |word: &str| {
+        let word = word.trim_start_matches('(');
+        word.matches('(').count() as i32 - word.matches(')').count() as i32
+    };
+    let is_data_name = |word: &str| {
+        let mut found = false;
+        walk_data(look_up, &mut vec![], &mut |_, data| {
+            found |= &*data.name == word
+        });
+        found
+    };
+
+    let mut joined: Vec<Arc<str>> = vec![];
+    let mut words = words.peekable();
+    while let Some(word) = words.next() {
+        if word.starts_with('"') {
+            joined.push(word);
+            continue;
+        }
+
+        let mut word = String::from(&*word);
+        let spaced = word.starts_with('(') && joined.last().is_some_and(|w| is_data_name(w));
+        if spaced {
+            word = format!("{}{}", joined.pop().unwrap(), word);
+        }
+        while depth(&word) > 0 {
+            match words.next_if(|w| &**w != ".") {
+                Some(next) => word = format!("{word} {next}"),
+                None => panic!("missing ) after {word}"),
+            }
+        }
+        joined.push(Arc::from(word));
+    }
+
+    joined
+}
+
+// a qualified name is made into a single word, so `a of b in c` is read as the one word `a of b of c`,
+// and so is all followed by the literal or figurative constant it repeats
+fn join_qualified(words: impl Iterator<Item = Arc<str>>) -> VecDeque<Arc<str>> {
+    let mut joined: VecDeque<Arc<str>> = VecDeque::new();
+    let mut words = words.peekable();
+    while let Some(word) = words.next() {
+        if &*word == "all" {
+            let repeated = words.next_if(|w| w.starts_with('"') || Figurative::parse(w).is_some());
+            if let Some(repeated) = repeated {
+                joined.push_back(Arc::from(format!("all {repeated}")));
+                continue;
+            }
+        }
+
+        let qualifies = matches!(&*word, "of" | "in") && joined.back().is_some_and(|w| &**w != ".");
+        match words.next_if(|_| qualifies) {
+            Some(group) => {
+                let name = joined.pop_back().unwrap();
+                joined.push_back(Arc::from(format!("{name} of {group}")));
+            }
+            None => joined.push_back(word),
+        }
+    }
+
+    joined
 }
 
+// splits a line into words, with the period that ends a sentence pulled out into its own word
 fn walk_line(line: &str) -> Vec<Arc<str>> {
-    let trimmed = line.trim_start();
-    let words = get_words(trimmed);
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return vec![];
+    }
+
+    let mut words = vec![];
+    for word in get_words(trimmed) {
+        if word.len() > 1 && !word.starts_with('"') && word.ends_with('.') {
+            words.push(Arc::from(word.strip_suffix('.').unwrap()));
+            words.push(Arc::from("."));
+        } else {
+            words.push(word);
+        }
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{Data, Parser, Section, Usage};
+
+    fn parse(storage: &str, procedure: &str) -> (Vec<Data>, Vec<Section>) {
+        let source = format!(
+            "identification division.
+program-id. test.
+data division.
+working-storage section.
+{storage}
+procedure division.
+{procedure}"
+        );
+        Parser::new(&source).parse()
+    }
+
+    #[test]
+    fn test_group_usage() {
+        let (data, _) = parse(
+            "01 amounts comp-3.
+    05 total pic s9(5).
+    05 counts.
+        10 count-a pic 9(3).
+    05 label-a pic x(2).
+    05 whole pic 9(4) binary.",
+            "stop run.",
+        );
+        let amounts = &data[0].children;
+        assert_eq!(amounts[0].usage, Usage::PackedDecimal);
+        assert_eq!(amounts[1].children[0].usage, Usage::PackedDecimal);
+        assert_eq!(amounts[2].usage, Usage::Display);
+        assert_eq!(amounts[3].usage, Usage::Binary);
+        assert_eq!(data[0].size(), 3 + 2 + 2 + 2);
+    }
+
+    #[test]
+    fn test_sign_clause() {
+        let (data, _) = parse(
+            "01 amounts sign leading separate.
+    05 total pic s9(5) sync.
+    05 count-a pic 9(3).
+    05 last-a pic s9(3) sign is trailing separate character.
+    05 first-a pic s9(3) leading.",
+            "stop run.",
+        );
+        let amounts = &data[0].children;
+        assert_eq!(amounts[0].usage, Usage::LeadingSeparate);
+        assert_eq!(amounts[1].usage, Usage::Display);
+        assert_eq!(amounts[2].usage, Usage::TrailingSeparate);
+        assert_eq!(amounts[3].usage, Usage::LeadingSign);
+        assert_eq!(data[0].size(), 6 + 3 + 4 + 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 6: zero-a has a blank clause, which is not supported")]
+    fn test_unsupported_clause() {
+        parse(
+            "01 record-a.
+    05 zero-a pic 9(3) blank when zero.",
+            "stop run.",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "line 5: total-a has 50, which is not a level number")]
+    fn test_level_number() {
+        parse("50 total-a pic 9.", "stop run.");
+    }
 
-    return words;
+    #[test]
+    #[should_panic(expected = "line 5: total-a has picture 9x$, which is not supported")]
+    fn test_unsupported_picture() {
+        parse("01 total-a pic 9x$.", "stop run.");
+    }
 }
@@ -1,165 +1,2081 @@
-use crate::parser::{Condition, Data, DataType, IdentifierType, Infix, Instruction, Value};
+use crate::flow::Flow;
+use crate::parser::{
+    byte_string, count_of, format_decimal, length_accessor, rust_name, walk_data, Arithmetic,
+    Class, Condition, ConditionName, ConditionValue, Count, Data, DataType, Expression, Figurative,
+    Ident, IdentifierType, Index, Infix, Instruction, Object, Operator, Receiver, Receiving,
+    Relation, Rounding, Section, Sign, SizeError, Subject, Subscript, Usage, Value, Varying, When,
+};
 
 use std::sync::Arc;
 
-fn value_to_string(left: Value) -> (String, Option<Arc<str>>) {
+fn value_to_string(left: Value) -> String {
     match left {
-        Value::Number(i) => (format!("{}", i), None),
-        Value::Identifier(ident) => (String::from(&*ident.name), Some(ident.name.clone())),
-        Value::String(str) => (String::from(&*str), None),
+        Value::Number(i) => format!("{}", i),
+        Value::Decimal { value, scale } => format_decimal(value, scale),
+        Value::Identifier(ident) => ident.to_string(),
+        Value::String(str) => String::from(&*str),
+        Value::Figurative(_) => {
+            unreachable!("a figurative constant takes the length of what it is used with")
+        }
+    }
+}
+
+// arithmetic is done on decimals, which every numeric field can be read as
+fn decimal_operand(value: &Value) -> String {
+    match value {
+        Value::Number(i) => format!("Decimal::from({})", i),
+        Value::Decimal { value, scale } => format!("Decimal::new({}, {})", value, scale),
+        Value::Identifier(Ident {
+            kind: IdentifierType::Numeric(_),
+            ..
+        }) => format!("{}.decimal()", value),
+        Value::Figurative(Figurative::Zero) => String::from("Decimal::zero()"),
+        _ => panic!("{} is not numeric", value),
+    }
+}
+
+// the sources of an arithmetic statement added up, which is what add and subtract work with
+fn decimal_sum(values: &[Value]) -> String {
+    let operands: Vec<String> = values.iter().map(decimal_operand).collect();
+    if operands.len() == 1 {
+        operands[0].clone()
+    } else {
+        format!("({})", operands.join(" + "))
+    }
+}
+
+// the bytes of a figurative constant (or all literal) as a byte string
+fn figurative_pattern(figurative: &Figurative) -> String {
+    byte_string(&figurative.bytes())
+}
+
+// the value as the bytes an alphanumeric field would hold, which for a numeric field are its
+// digits without a sign. a group move takes the bytes of a field as they are instead
+fn alphanumeric_operand(value: &Value, receiver: &Ident, group: bool) -> String {
+    match value {
+        Value::Identifier(Ident {
+            kind: IdentifierType::Numeric(_),
+            ..
+        }) if !group => format!("&{}.to_zeroed_string().into_bytes()", value),
+        // a move between a group and an item inside of it has to copy the bytes out first, since
+        // the receiver cannot be written while they are borrowed
+        Value::Identifier(ident) if ident.overlaps(receiver) => {
+            format!("&{}.to_vec()", ident.bytes())
+        }
+        Value::Identifier(ident) => ident.bytes(),
+        Value::Number(i) if !group => byte_string(i.unsigned_abs().to_string().as_bytes()),
+        value => byte_string(value_to_string(value.clone()).as_bytes()),
+    }
+}
+
+// the value as the number a numeric field would hold. text is read as an unsigned integer, and an
+// edited field is read back by its picture
+fn numeric_operand(value: &Value) -> String {
+    match value {
+        Value::Identifier(Ident {
+            kind: IdentifierType::NumericEdited(picture),
+            ..
+        }) => format!("de_edit({}.bytes(), \"{}\")", value, picture.symbols),
+        Value::Identifier(Ident {
+            kind: IdentifierType::Alphanumeric(_),
+            ..
+        }) => format!("{}.alphanumeric().decimal()", value),
+        Value::String(_) => format!("Alphanumeric::from(&{}[..]).decimal()", value),
+        value => decimal_operand(value),
+    }
+}
+
+// calls a method that stores value into the receiver, with whatever other arguments it takes after
+// it. an accessor borrows the whole group (or program) it is in, so when the receiver is reached
+// through one the value is worked out first
+fn store(receiver: &Ident, method: &str, value: &str, arguments: &str) -> String {
+    if receiver.views.is_empty() {
+        format!("{}.{}({}{})", receiver.mutable(), method, value, arguments)
+    } else {
+        format!(
+            "{{\nlet value = {};\n{}.{}(value{})\n}}",
+            value,
+            receiver.mutable(),
+            method,
+            arguments
+        )
+    }
+}
+
+// what a move sends, as far as which moves are allowed and how the value is converted go
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Sending {
+    Alphabetic,
+    Alphanumeric,
+    AlphanumericEdited,
+    Integer,
+    Fraction,
+    NumericEdited,
+    Group,
+    Zero,
+    Space,
+    Figurative,
+}
+
+impl Sending {
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::Number(_) => Sending::Integer,
+            Value::Decimal { scale: 0, .. } => Sending::Integer,
+            Value::Decimal { .. } => Sending::Fraction,
+            Value::String(_) => Sending::Alphanumeric,
+            Value::Figurative(Figurative::Zero) => Sending::Zero,
+            Value::Figurative(Figurative::Space) => Sending::Space,
+            Value::Figurative(_) => Sending::Figurative,
+            Value::Identifier(ident) => match &ident.kind {
+                IdentifierType::Numeric(picture) if picture.scale > 0 => Sending::Fraction,
+                IdentifierType::Numeric(_) => Sending::Integer,
+                IdentifierType::NumericEdited(_) => Sending::NumericEdited,
+                IdentifierType::Alphabetic(_) => Sending::Alphabetic,
+                IdentifierType::Alphanumeric(_) => Sending::Alphanumeric,
+                IdentifierType::AlphanumericEdited(_) => Sending::AlphanumericEdited,
+                IdentifierType::Group => Sending::Group,
+            },
+        }
+    }
+
+    // whether the receiver can take what is sent. anything goes to and from a group, which is a
+    // move of bytes. numbers cannot be sent to alphabetic items, numbers with a fraction not to
+    // anything that holds text, and nothing but numbers, unsigned text and zero to numeric ones
+    fn can_move_to(self, receiver: &IdentifierType) -> bool {
+        use IdentifierType as Receiving;
+        match (self, receiver) {
+            (Sending::Group, _) | (_, Receiving::Group) => true,
+            (Sending::Zero, Receiving::Alphabetic(_)) => false,
+            (Sending::Zero, _) => true,
+            (
+                Sending::Space
+                | Sending::Figurative
+                | Sending::Alphabetic
+                | Sending::AlphanumericEdited,
+                Receiving::Numeric(_) | Receiving::NumericEdited(_),
+            ) => false,
+            (
+                Sending::Integer | Sending::Fraction | Sending::NumericEdited,
+                Receiving::Alphabetic(_),
+            ) => false,
+            (Sending::Fraction, Receiving::Alphanumeric(_) | Receiving::AlphanumericEdited(_)) => {
+                false
+            }
+            _ => true,
+        }
+    }
+
+    fn describe(self, value: &Value) -> String {
+        let category = match self {
+            Sending::Alphabetic => "alphabetic",
+            Sending::Alphanumeric => "alphanumeric",
+            Sending::AlphanumericEdited => "alphanumeric edited",
+            Sending::Integer => "numeric",
+            Sending::Fraction => "non-integer numeric",
+            Sending::NumericEdited => "numeric edited",
+            Sending::Group => "group",
+            Sending::Zero => return String::from("zero"),
+            Sending::Space => return String::from("space"),
+            Sending::Figurative => return String::from("a figurative constant"),
+        };
+        match value {
+            Value::Identifier(ident) => format!("{} item {}", category, ident.name),
+            value => format!("{} literal {}", category, value_to_string(value.clone())),
+        }
     }
 }
 
-fn generate_move(infix: Infix) -> (String, Vec<Arc<str>>) {
-    let mut possible_idents_to_generate = vec![];
-    possible_idents_to_generate.push(infix.right.name.clone());
+// the category of an item as a diagnostic names it
+fn category(kind: &IdentifierType) -> &'static str {
+    match kind {
+        IdentifierType::Numeric(_) => "numeric",
+        IdentifierType::NumericEdited(_) => "numeric edited",
+        IdentifierType::Alphabetic(_) => "alphabetic",
+        IdentifierType::Alphanumeric(_) => "alphanumeric",
+        IdentifierType::AlphanumericEdited(_) => "alphanumeric edited",
+        IdentifierType::Group => "group",
+    }
+}
+
+// text is moved in from the left, padded with spaces and cut off on the right, unless the receiver
+// is justified right. a numeric receiver lines the value up on the decimal point and drops the
+// digits on either end that do not fit, and an edited one is given the value as its picture
+// formats it, where an edited value is read back by its picture first. a move from or to a group
+// is a move of the bytes, whatever the items in the group are. a figurative constant fills the
+// receiver, apart from zero into a numeric receiver, which is a number like any other
+fn generate_move(infix: Infix) -> String {
+    let Infix { left, right } = infix;
+    let sending = Sending::of(&left);
+    if !sending.can_move_to(&right.kind) {
+        panic!(
+            "cannot move {} to {} item {}",
+            sending.describe(&left),
+            category(&right.kind),
+            right.name
+        );
+    }
+
+    let numeric = matches!(
+        right.kind,
+        IdentifierType::Numeric(_) | IdentifierType::NumericEdited(_)
+    );
+    if let Value::Figurative(figurative) = &left {
+        if !numeric || *figurative != Figurative::Zero {
+            let pattern = figurative_pattern(figurative);
+            return format!("{};\n", store(&right, "fill", &pattern, ""));
+        }
+    }
+
+    let group = sending == Sending::Group || matches!(right.kind, IdentifierType::Group);
+    let text = if right.justified {
+        "set_justified"
+    } else {
+        "set_text"
+    };
+    let (method, argument) = match &right.kind {
+        _ if group => (text, alphanumeric_operand(&left, &right, true)),
+        IdentifierType::Numeric(_) => ("set", numeric_operand(&left)),
+        IdentifierType::NumericEdited(picture) => (
+            "set_text",
+            format!(
+                "&edit_numeric({}, \"{}\").into_bytes()",
+                numeric_operand(&left),
+                picture.symbols
+            ),
+        ),
+        IdentifierType::AlphanumericEdited(picture) => (
+            "set_text",
+            format!(
+                "&edit_alphanumeric({}, \"{}\").into_bytes()",
+                alphanumeric_operand(&left, &right, false),
+                picture.symbols
+            ),
+        ),
+        _ => (text, alphanumeric_operand(&left, &right, false)),
+    };
+
+    format!("{};\n", store(&right, method, &argument, ""))
+}
+
+// a move into every entry of the tables the receiver goes through, with a loop for each of them
+// that counts its entry from zero. a table with depending on only goes as far as the entries in use
+fn generate_every_entry(infix: Infix) -> String {
+    let loops: Vec<String> = infix
+        .right
+        .subscripts
+        .iter()
+        .filter(|s| matches!(s.index, Index::Every))
+        .map(|subscript| {
+            let count = match &subscript.count {
+                Count::Fixed(max) => max.to_string(),
+                Count::Depending(ident) => format!("{}.decimal().truncate() as usize", ident),
+            };
+            format!(
+                "for {} in 0..{} {{
+",
+                subscript.entry(),
+                count
+            )
+        })
+        .collect();
+    format!(
+        "{}{}{}",
+        loops.concat(),
+        generate_move(infix),
+        "}\n".repeat(loops.len())
+    )
+}
 
-    let (left, push_infix) = value_to_string(infix.left);
-    if let Some(inf) = push_infix {
-        possible_idents_to_generate.push(inf);
+// the rounding mode as the runtime names it
+fn rounding_mode(rounding: Rounding) -> &'static str {
+    match rounding {
+        Rounding::AwayFromZero => "Rounding::AwayFromZero",
+        Rounding::NearestAwayFromZero => "Rounding::NearestAwayFromZero",
+        Rounding::NearestEven => "Rounding::NearestEven",
+        Rounding::NearestTowardZero => "Rounding::NearestTowardZero",
+        Rounding::Prohibited => "Rounding::Prohibited",
+        Rounding::TowardGreater => "Rounding::TowardGreater",
+        Rounding::TowardLesser => "Rounding::TowardLesser",
+        Rounding::Truncation => "Rounding::Truncation",
     }
+}
+
+// stores a result into a receiver, rounding it first when asked to. a checked store leaves the
+// receiver alone when the result does not fit it, and remembers that in size_error instead
+fn store_result(receiver: &Receiver, result: &str, checked: bool) -> String {
+    let (method, arguments) = match receiver.rounding {
+        Some(rounding) => ("set_rounded", format!(", {}", rounding_mode(rounding))),
+        None => ("set", String::new()),
+    };
 
-    let text = if let IdentifierType::Alphanumeric(_) = infix.right.kind {
-        format!("{} = {}.to_zeroed_string();\n", infix.right.name, left)
+    if checked {
+        let method = format!("try_{}", method);
+        let store = store(&receiver.ident, &method, result, &arguments);
+        format!("size_error |= !{};\n", store)
     } else {
-        format!("{} = {}.into();\n", infix.right.name, left)
+        format!("{};\n", store(&receiver.ident, method, result, &arguments))
+    }
+}
+
+// a statement with a size error phrase keeps track of whether any of its results did not fit, and
+// ends by running whichever of its phrases applies
+fn generate_size_error(body: String, size_error: SizeError, procedures: &Procedures) -> String {
+    if !size_error.is_checked() {
+        return format!("{{\n{}}}\n", body);
+    }
+
+    let on = translate_core(size_error.on.unwrap_or_default(), procedures);
+    let not_on = translate_core(size_error.not_on.unwrap_or_default(), procedures);
+    let handlers = match (on.is_empty(), not_on.is_empty()) {
+        (_, true) => format!("if size_error {{\n{}}}\n", on),
+        (true, false) => format!("if !size_error {{\n{}}}\n", not_on),
+        (false, false) => format!("if size_error {{\n{}}} else {{\n{}}}\n", on, not_on),
     };
 
-    (text, possible_idents_to_generate)
+    format!("{{\nlet mut size_error = false;\n{}{}}}\n", body, handlers)
+}
+
+// the addends are added up once, before any receiver is changed, so a receiver that is also one of
+// them is only added the way it was at the start
+fn generate_add(sources: &[Value], receiving: Receiving, checked: bool) -> String {
+    let addend = decimal_sum(sources);
+    match receiving {
+        Receiving::Receivers(receivers) => {
+            let mut text = format!("let addend = {};\n", addend);
+            for receiver in receivers {
+                let sum = format!("{}.decimal() + addend", receiver.ident);
+                text += &store_result(&receiver, &sum, checked);
+            }
+            text
+        }
+        Receiving::Giving { operand, receivers } => {
+            let sum = match operand {
+                Some(operand) => format!("{} + {}", addend, decimal_operand(&operand)),
+                None => addend,
+            };
+            let mut text = format!("let sum = {};\n", sum);
+            for receiver in receivers {
+                text += &store_result(&receiver, "sum", checked);
+            }
+            text
+        }
+        Receiving::Corresponding(pairs) => {
+            let mut text = String::new();
+            for (source, receiver) in pairs {
+                let sum = format!(
+                    "{}.decimal() + {}",
+                    receiver.ident,
+                    decimal_operand(&source)
+                );
+                text += &store_result(&receiver, &sum, checked);
+            }
+            text
+        }
+    }
+}
+
+fn generate_multiply(sources: &[Value], receiving: Receiving, checked: bool) -> String {
+    let [multiplier] = sources else {
+        unreachable!()
+    };
+
+    let mut text = format!("let multiplier = {};\n", decimal_operand(multiplier));
+    match receiving {
+        Receiving::Receivers(receivers) => {
+            for receiver in receivers {
+                let product = format!("multiplier * {}.decimal()", receiver.ident);
+                text += &store_result(&receiver, &product, checked);
+            }
+        }
+        Receiving::Giving {
+            operand: Some(multiplicand),
+            receivers,
+        } => {
+            text += &format!(
+                "let product = multiplier * {};\n",
+                decimal_operand(&multiplicand)
+            );
+            for receiver in receivers {
+                text += &store_result(&receiver, "product", checked);
+            }
+        }
+        Receiving::Giving { operand: None, .. } | Receiving::Corresponding(_) => unreachable!(),
+    }
+
+    text
 }
 
-fn generate_add(infix: Infix) -> String {
-    let needs_ref = match &infix.left {
-        Value::Number(_) => "",
-        Value::Identifier(_) => "&",
-        Value::String(_) => "",
+// the subtrahends are added up once, before any receiver is changed, so a receiver that is also
+// one of them is only subtracted the way it was at the start
+fn generate_subtract(sources: &[Value], receiving: Receiving, checked: bool) -> String {
+    let subtrahend = decimal_sum(sources);
+    match receiving {
+        Receiving::Receivers(receivers) => {
+            let mut text = format!("let subtrahend = {};\n", subtrahend);
+            for receiver in receivers {
+                let difference = format!("{}.decimal() - subtrahend", receiver.ident);
+                text += &store_result(&receiver, &difference, checked);
+            }
+            text
+        }
+        Receiving::Giving {
+            operand: Some(minuend),
+            receivers,
+        } => {
+            let mut text = format!(
+                "let difference = {} - {};\n",
+                decimal_operand(&minuend),
+                subtrahend
+            );
+            for receiver in receivers {
+                text += &store_result(&receiver, "difference", checked);
+            }
+            text
+        }
+        Receiving::Corresponding(pairs) => {
+            let mut text = String::new();
+            for (source, receiver) in pairs {
+                let difference = format!(
+                    "{}.decimal() - {}",
+                    receiver.ident,
+                    decimal_operand(&source)
+                );
+                text += &store_result(&receiver, &difference, checked);
+            }
+            text
+        }
+        Receiving::Giving { operand: None, .. } => unreachable!(),
+    }
+}
+
+// dividing by zero is a size error, which leaves the receivers as they were. the remainder is what
+// is left of the dividend after taking away the divisor times the quotient as it fits its receiver,
+// before any rounding, and it is only stored when the quotient could be
+fn generate_divide(
+    sources: &[Value],
+    receiving: Receiving,
+    remainder: Option<Ident>,
+    checked: bool,
+) -> String {
+    let [divisor] = sources else { unreachable!() };
+    let by_zero = if checked {
+        " else {\nsize_error = true;\n}\n"
+    } else {
+        "\n"
     };
 
-    let (left, _) = value_to_string(infix.left);
+    let mut text = format!("let divisor = {};\n", decimal_operand(divisor));
+    match receiving {
+        Receiving::Receivers(receivers) => {
+            for receiver in receivers {
+                text += &format!(
+                    "if let Some(quotient) = {}.decimal().divide(divisor) {{\n{}}}{}",
+                    receiver.ident,
+                    store_result(&receiver, "quotient", checked),
+                    by_zero
+                );
+            }
+        }
+        Receiving::Giving {
+            operand: Some(dividend),
+            receivers,
+        } => {
+            text += &format!(
+                "let dividend = {};\nif let Some(quotient) = dividend.divide(divisor) {{\n",
+                decimal_operand(&dividend)
+            );
+            for receiver in &receivers {
+                text += &store_result(receiver, "quotient", checked);
+            }
+            if let Some(remainder) = remainder {
+                let receiver = Receiver {
+                    ident: remainder,
+                    rounding: None,
+                };
+                let store = store_result(
+                    &receiver,
+                    &format!(
+                        "dividend - quotient.truncate_to({}) * divisor",
+                        field_scale(&receivers[0].ident)
+                    ),
+                    checked,
+                );
+                text += &if checked {
+                    format!("if !size_error {{\n{}}}\n", store)
+                } else {
+                    store
+                };
+            }
+            text += &format!("}}{}", by_zero);
+        }
+        Receiving::Giving { operand: None, .. } | Receiving::Corresponding(_) => unreachable!(),
+    }
 
-    return format!("{} += {needs_ref}{};\n", infix.right.name, left);
+    text
 }
 
-fn generate_multiply(infix: Infix) -> String {
-    let (left, _) = value_to_string(infix.left);
+// division and exponentiation are the operations that can fail
+fn is_fallible(expression: &Expression) -> bool {
+    match expression {
+        Expression::Value(_) | Expression::Literal { .. } => false,
+        Expression::Negate(operand) => is_fallible(operand),
+        Expression::Binary {
+            left,
+            operator,
+            right,
+        } => {
+            matches!(operator, Operator::Divide | Operator::Power)
+                || is_fallible(left)
+                || is_fallible(right)
+        }
+    }
+}
 
-    return format!(
-        "{name} *= {name}.convert(&{mult});\n",
-        name = infix.right.name,
-        mult = left
+// an expression as arithmetic on decimals. a failing division or exponentiation returns early
+// through the ?, so an expression using them has to be evaluated inside of something returning an
+// option
+fn generate_expression(expression: &Expression) -> String {
+    match expression {
+        Expression::Value(value) => decimal_operand(value),
+        Expression::Literal { value, scale } => format!("Decimal::new({}, {})", value, scale),
+        Expression::Negate(operand) => format!("-{}", nested_expression(operand)),
+        Expression::Binary {
+            left,
+            operator,
+            right,
+        } => {
+            let left = nested_expression(left);
+            let right = nested_expression(right);
+            match operator {
+                Operator::Add => format!("{} + {}", left, right),
+                Operator::Subtract => format!("{} - {}", left, right),
+                Operator::Multiply => format!("{} * {}", left, right),
+                Operator::Divide => format!("{}.divide({})?", left, right),
+                Operator::Power => format!("{}.power({})?", left, right),
+            }
+        }
+    }
+}
+
+// every operation inside of another one is parenthesised, which keeps the order of evaluation
+// the expression was parsed with
+fn nested_expression(expression: &Expression) -> String {
+    match expression {
+        Expression::Binary { .. } | Expression::Negate(_) => {
+            format!("({})", generate_expression(expression))
+        }
+        _ => generate_expression(expression),
+    }
+}
+
+// the expression is worked out once and then stored into every receiver. when any division or
+// exponentiation in it fails that is a size error, which leaves the receivers as they were
+fn generate_compute(receivers: &[Receiver], expression: &Expression, checked: bool) -> String {
+    let stores: String = receivers
+        .iter()
+        .map(|receiver| store_result(receiver, "result", checked))
+        .collect();
+
+    let expression_text = generate_expression(expression);
+    if !is_fallible(expression) {
+        format!("let result = {};\n{}", expression_text, stores)
+    } else if checked {
+        format!(
+            "match (|| Some({}))() {{\nSome(result) => {{\n{}}}\nNone => size_error = true,\n}}\n",
+            expression_text, stores
+        )
+    } else {
+        format!(
+            "if let Some(result) = (|| Some({}))() {{\n{}}}\n",
+            expression_text, stores
+        )
+    }
+}
+
+fn generate_repeat(condition: Condition, test_after: bool, body: String) -> String {
+    let test = format!("if {} {{\nbreak;\n}}\n", generate_condition(condition));
+    if test_after {
+        format!("loop {{\n{}{}}}\n", body, test)
+    } else {
+        format!("loop {{\n{}{}}}\n", test, body)
+    }
+}
+
+// the count is evaluated once on entry, changing it inside of the loop does not change how often
+// the loop runs. only the integer part of it counts, and a count below one runs the loop no times
+fn generate_times(count: Value, body: String) -> String {
+    let count = match count {
+        Value::Number(i) => i.max(0).to_string(),
+        Value::Identifier(Ident {
+            kind: IdentifierType::Numeric(_),
+            ..
+        }) => format!("{}.decimal().truncate().max(0)", count),
+        _ => panic!("perform {} times needs a numeric count", count),
+    };
+
+    format!("for _ in 0..{} {{\n{}}}\n", count, body)
+}
+
+// every varying is set to its from value up front. each loop then tests its condition (after its
+// inner loops when testing after), and once an inner loop is done its varying is set back to its
+// from value before the outer one is augmented, following the 1985 standard
+fn generate_varying(varyings: &[Varying], test_after: bool, body: String) -> String {
+    let set = |varying: &Varying| {
+        generate_move(Infix {
+            left: varying.from.clone(),
+            right: varying.ident.clone(),
+        })
+    };
+
+    let mut text = body;
+    for (index, varying) in varyings.iter().enumerate().rev() {
+        let receiver = Receiver {
+            ident: varying.ident.clone(),
+            rounding: None,
+        };
+        let augment = format!(
+            "{{\n{}}}\n",
+            generate_add(
+                std::slice::from_ref(&varying.by),
+                Receiving::Receivers(vec![receiver]),
+                false
+            )
+        );
+        let reset = varyings.get(index + 1).map(set).unwrap_or_default();
+        let test = format!(
+            "if {} {{\nbreak;\n}}\n",
+            generate_condition(varying.until.clone())
+        );
+
+        text = if test_after {
+            format!("loop {{\n{}{}{}{}}}\n", text, test, reset, augment)
+        } else {
+            format!("loop {{\n{}{}{}{}}}\n", test, text, reset, augment)
+        };
+    }
+
+    let initial: String = varyings.iter().map(set).collect();
+    initial + &text
+}
+
+// how many places after the decimal point a numeric field keeps
+fn field_scale(ident: &Ident) -> i32 {
+    match ident.kind {
+        IdentifierType::Numeric(picture) => picture.scale,
+        _ => 0,
+    }
+}
+
+// two numeric operands are compared as decimals, so fields of different sizes can be compared
+// with each other. anything else is compared alphanumerically, with the shorter operand padded with
+// spaces, and a numeric field takes part in that through its digits
+fn comparison_operand(value: &Value, other: &Value) -> String {
+    if is_numeric(value) && is_numeric(other) {
+        return decimal_operand(value);
+    }
+
+    match value {
+        // as long as what it is compared with, which for a numeric field are its digits
+        Value::Figurative(figurative) => {
+            let length = match other {
+                Value::Identifier(Ident {
+                    kind: IdentifierType::Numeric(_),
+                    ..
+                }) => format!("{}.to_zeroed_string().len()", other),
+                Value::Identifier(ident) => format!("{}.len()", ident.bytes()),
+                Value::Figurative(_) => String::from("1"),
+                other => value_to_string(other.clone()).len().to_string(),
+            };
+            format!(
+                "Alphanumeric::from(&figurative({}, {})[..])",
+                figurative_pattern(figurative),
+                length
+            )
+        }
+        Value::Identifier(Ident {
+            kind: IdentifierType::Numeric(_),
+            ..
+        }) => format!("Alphanumeric::from({}.to_zeroed_string().as_str())", value),
+        Value::Identifier(ident) => ident.alphanumeric(),
+        value => format!(
+            "Alphanumeric::from(&{}[..])",
+            byte_string(value_to_string(value.clone()).as_bytes())
+        ),
+    }
+}
+
+fn is_numeric(value: &Value) -> bool {
+    match value {
+        Value::Number(_) | Value::Decimal { .. } => true,
+        Value::Identifier(ident) => matches!(ident.kind, IdentifierType::Numeric(_)),
+        Value::Figurative(figurative) => *figurative == Figurative::Zero,
+        Value::String(_) => false,
+    }
+}
+
+fn generate_condition(condition: Condition) -> String {
+    match condition {
+        Condition::Relation {
+            left,
+            relation,
+            right,
+        } => {
+            let left_string = comparison_operand(&left, &right);
+            let right_string = comparison_operand(&right, &left);
+            format!("{} {} {}", left_string, relation, right_string)
+        }
+        Condition::And(left, right) => format!(
+            "{} && {}",
+            nested_condition(*left, true),
+            nested_condition(*right, true)
+        ),
+        Condition::Or(left, right) => format!(
+            "{} || {}",
+            nested_condition(*left, false),
+            nested_condition(*right, false)
+        ),
+        Condition::Not(condition) => format!("!({})", generate_condition(*condition)),
+        Condition::Named { name, parent } => {
+            format!("self.{}({})", rust_name(&name), condition_entry(&parent))
+        }
+        Condition::Class { value, class } => generate_class(value, class),
+        Condition::Sign { value, sign } => generate_sign(value, sign),
+    }
+}
+
+// class tests look at what is actually stored in the field. a numeric field is checked by its
+// usage, which knows where its digits and sign are
+fn generate_class(value: Value, class: Class) -> String {
+    let Value::Identifier(ident) = value else {
+        panic!("class condition on literal {}", value);
+    };
+
+    if let IdentifierType::Numeric(_) = ident.kind {
+        if !matches!(class, Class::Numeric) {
+            panic!("alphabetic class condition on numeric field {}", ident.name);
+        }
+        return format!("{}.is_numeric()", ident);
+    }
+
+    let contents = format!("{}.iter().copied()", ident.bytes());
+
+    let check = match class {
+        Class::Numeric => "b.is_ascii_digit()",
+        Class::Alphabetic => "b.is_ascii_alphabetic() || b == b' '",
+        Class::AlphabeticLower => "b.is_ascii_lowercase() || b == b' '",
+        Class::AlphabeticUpper => "b.is_ascii_uppercase() || b == b' '",
+    };
+
+    format!("{}.all(|b| {})", contents, check)
+}
+
+fn generate_sign(value: Value, sign: Sign) -> String {
+    let zero = match &value {
+        Value::Number(_) | Value::Decimal { .. } => "0",
+        Value::Identifier(Ident {
+            kind: IdentifierType::Numeric(_),
+            ..
+        }) => "Decimal::zero()",
+        _ => panic!("sign condition on non numeric {}", value),
+    };
+
+    let relation = match sign {
+        Sign::Positive => ">",
+        Sign::Negative => "<",
+        Sign::Zero => "==",
+    };
+
+    format!(
+        "{} {} {}",
+        comparison_operand(&value, &value),
+        relation,
+        zero
+    )
+}
+
+// an and inside of an or (or the other way around) is parenthesised, so the generated code keeps
+// the grouping of the original
+fn nested_condition(condition: Condition, inside_and: bool) -> String {
+    match condition {
+        Condition::And(..) if !inside_and => format!("({})", generate_condition(condition)),
+        Condition::Or(..) if inside_and => format!("({})", generate_condition(condition)),
+        _ => generate_condition(condition),
+    }
+}
+
+// numeric fields leave off the trailing generic arguments that are the runtime's defaults, so a
+// plain pic 9(n) field is just a Num<n>
+fn field_type(kind: &IdentifierType, usage: Usage) -> String {
+    let picture = match kind {
+        IdentifierType::Numeric(picture) => picture,
+        IdentifierType::Alphabetic(length) | IdentifierType::Alphanumeric(length) => {
+            return format!("Text<{}>", length);
+        }
+        IdentifierType::NumericEdited(picture) | IdentifierType::AlphanumericEdited(picture) => {
+            return format!("Text<{}>", picture.length);
+        }
+        _ => unreachable!(),
+    };
+
+    let usage_name = match usage {
+        Usage::Display => "usage::Display",
+        Usage::Binary => "usage::Binary",
+        Usage::PackedDecimal => "usage::PackedDecimal",
+        Usage::NativeBinary => "usage::NativeBinary",
+        Usage::LeadingSign => "usage::LeadingSign",
+        Usage::LeadingSeparate => "usage::LeadingSeparate",
+        Usage::TrailingSeparate => "usage::TrailingSeparate",
+    };
+    let arguments = [
+        picture.digits.to_string(),
+        picture.scale.to_string(),
+        picture.signed.to_string(),
+        usage_name.to_string(),
+        usage.size(picture.digits).to_string(),
+    ];
+    let defaults = [
+        String::new(),
+        String::from("0"),
+        String::from("false"),
+        String::from("usage::Display"),
+        picture.digits.to_string(),
+    ];
+    let mut used = arguments.len();
+    while used > 1 && arguments[used - 1] == defaults[used - 1] {
+        used -= 1;
+    }
+
+    format!("Num<{}>", arguments[..used].join(", "))
+}
+
+// a value of a condition name as what its parent is compared with, which is a number when the
+// parent is numeric and text otherwise
+fn condition_literal(value: &Value, parent: &Ident) -> String {
+    let numeric = matches!(
+        value,
+        Value::Number(_) | Value::Decimal { .. } | Value::Figurative(Figurative::Zero)
     );
+    match (&parent.kind, value) {
+        (IdentifierType::Numeric(_), _) if numeric => decimal_operand(value),
+        (IdentifierType::Numeric(_), _) | (_, Value::Identifier(_)) => {
+            panic!("condition value {} does not fit its field", value)
+        }
+        _ => comparison_operand(value, &Value::Identifier(parent.clone())),
+    }
 }
 
-fn generate_repeat(left: Value, condition: Condition, right: Value) -> String {
-    let (left_string, _) = value_to_string(left);
-    let (right_string, _) = value_to_string(right);
+// every condition name becomes a predicate over its parent field, and a setter that moves the
+// first of its values into it. those of an item in a table take the entry of every table it is in,
+// counting from zero
+fn generate_condition_helpers(condition: &ConditionName, parent: &Ident) -> String {
+    let subject = match parent.kind {
+        IdentifierType::Numeric(_) => format!("{}.decimal()", parent),
+        _ => parent.alphanumeric(),
+    };
+
+    let checks: Vec<String> = condition
+        .values
+        .iter()
+        .map(|value| match value {
+            ConditionValue::Single(v) => {
+                format!("{} == {}", subject, condition_literal(v, parent))
+            }
+            ConditionValue::Range(low, high) => format!(
+                "({subject} >= {} && {subject} <= {})",
+                condition_literal(low, parent),
+                condition_literal(high, parent)
+            ),
+        })
+        .collect();
+
+    let first = match &condition.values[0] {
+        ConditionValue::Single(v) | ConditionValue::Range(v, _) => v.clone(),
+    };
+    let assignment = generate_move(Infix {
+        left: first,
+        right: parent.clone(),
+    });
+
+    let entries: Vec<String> = parent
+        .subscripts
+        .iter()
+        .map(|subscript| format!(", {}: usize", subscript.entry()))
+        .collect();
     format!(
-        "loop {{\nif {} {} {}{{\nbreak;}}",
-        left_string,
-        condition.to_string(),
-        right_string
+        "fn {name}(&self{entries}) -> bool {{\n{}\n}}\n\nfn set_{name}(&mut self{entries}) {{\n{}}}\n",
+        checks.join(" || "),
+        assignment,
+        name = rust_name(&condition.name),
+        entries = entries.concat(),
     )
 }
 
-fn translate_core(instructions: Vec<Instruction>) -> (Vec<Arc<str>>, String) {
-    let mut defined_variables = vec![];
+// the entries a condition name of an item in a table is about, as its helpers take them
+fn condition_entry(parent: &Ident) -> String {
+    let entries: Vec<String> = parent.subscripts.iter().map(|s| s.to_string()).collect();
+    entries.join(", ")
+}
+
+// an evaluate over alphanumeric fields compared only against literals can be a match on the
+// trimmed contents of the fields
+fn can_match(subjects: &[Subject], whens: &[When]) -> bool {
+    let subjects_match = subjects.iter().all(|subject| {
+        matches!(
+            subject,
+            Subject::Value(Value::Identifier(Ident {
+                kind: IdentifierType::Alphabetic(_) | IdentifierType::Alphanumeric(_),
+                ..
+            }))
+        )
+    });
+    let objects_match = whens
+        .iter()
+        .flat_map(|when| when.selections.iter().flatten())
+        .all(|object| {
+            matches!(
+                object,
+                Object::Any
+                    | Object::Value {
+                        value: Value::String(_),
+                        negated: false,
+                    }
+            )
+        });
+
+    subjects_match && objects_match
+}
+
+fn generate_match(
+    subjects: Vec<Subject>,
+    whens: Vec<When>,
+    other: Vec<Instruction>,
+    procedures: &Procedures,
+) -> String {
+    let scrutinees: Vec<String> = subjects
+        .iter()
+        .map(|subject| match subject {
+            Subject::Value(Value::Identifier(ident)) => {
+                format!("{}.trim_end()", ident.alphanumeric())
+            }
+            Subject::Value(value) => format!("{}.alphanumeric().trim_end()", value),
+            _ => unreachable!(),
+        })
+        .collect();
+
+    let mut text = if scrutinees.len() == 1 {
+        format!("match {} {{\n", scrutinees[0])
+    } else {
+        format!("match ({}) {{\n", scrutinees.join(", "))
+    };
+
+    for when in whens {
+        let patterns: Vec<String> = when
+            .selections
+            .iter()
+            .map(|objects| {
+                let patterns: Vec<String> = objects
+                    .iter()
+                    .map(|object| match object {
+                        Object::Value {
+                            value: Value::String(s),
+                            ..
+                        } => byte_string(s.trim_end_matches(' ').as_bytes()),
+                        _ => String::from("_"),
+                    })
+                    .collect();
+
+                if patterns.len() == 1 {
+                    patterns[0].clone()
+                } else {
+                    format!("({})", patterns.join(", "))
+                }
+            })
+            .collect();
+
+        let insts_text = translate_core(when.insts, procedures);
+        text += &format!("{} => {{\n{}}}\n", patterns.join(" | "), insts_text);
+    }
+
+    let other_text = translate_core(other, procedures);
+    text += &format!("_ => {{\n{}}}\n}}\n", other_text);
+
+    text
+}
+
+// the condition under which a single object selects its when, given the subject it lines up with
+fn object_condition(subject: &Subject, object: Object) -> Option<Condition> {
+    let relation = |left: &Value, relation, right: Value| Condition::Relation {
+        left: left.clone(),
+        relation,
+        right,
+    };
+    let negate = |condition, negated| {
+        if negated {
+            Condition::Not(Box::new(condition))
+        } else {
+            condition
+        }
+    };
+
+    match (subject, object) {
+        (_, Object::Any) => None,
+        (Subject::Value(subject), Object::Value { value, negated }) => {
+            Some(negate(relation(subject, Relation::EqualTo, value), negated))
+        }
+        (Subject::Value(subject), Object::Range { low, high, negated }) => Some(negate(
+            Condition::And(
+                Box::new(relation(subject, Relation::GreaterOrEqual, low)),
+                Box::new(relation(subject, Relation::LessOrEqual, high)),
+            ),
+            negated,
+        )),
+        (Subject::True, Object::Condition(condition)) => Some(condition),
+        (Subject::False, Object::Condition(condition)) => Some(Condition::Not(Box::new(condition))),
+        (Subject::Condition(condition), Object::True) => Some(condition.clone()),
+        (Subject::Condition(condition), Object::False) => {
+            Some(Condition::Not(Box::new(condition.clone())))
+        }
+        (Subject::True, Object::True) | (Subject::False, Object::False) => None,
+        _ => panic!("when object does not fit its evaluate subject"),
+    }
+}
+
+fn generate_if_chain(
+    subjects: Vec<Subject>,
+    whens: Vec<When>,
+    other: Vec<Instruction>,
+    procedures: &Procedures,
+) -> String {
+    let mut branches = vec![];
+
+    for when in whens {
+        let alternatives: Vec<String> = when
+            .selections
+            .into_iter()
+            .map(|objects| {
+                let conditions: Vec<Condition> = subjects
+                    .iter()
+                    .zip(objects)
+                    .filter_map(|(subject, object)| object_condition(subject, object))
+                    .collect();
+
+                match conditions
+                    .into_iter()
+                    .reduce(|left, right| Condition::And(Box::new(left), Box::new(right)))
+                {
+                    Some(condition) => generate_condition(condition),
+                    None => String::from("true"),
+                }
+            })
+            .collect();
+
+        let condition = if alternatives.len() == 1 {
+            alternatives[0].clone()
+        } else {
+            alternatives
+                .iter()
+                .map(|alternative| format!("({})", alternative))
+                .collect::<Vec<String>>()
+                .join(" || ")
+        };
+
+        let insts_text = translate_core(when.insts, procedures);
+        branches.push(format!("if {} {{\n{}}}", condition, insts_text));
+    }
+
+    let other_text = translate_core(other, procedures);
+
+    let mut text = branches.join(" else ");
+    if branches.is_empty() {
+        text = other_text;
+    } else if !other_text.is_empty() {
+        text += &format!(" else {{\n{}}}\n", other_text);
+    } else {
+        text += "\n";
+    }
+
+    text
+}
+
+// a paragraph that can be performed, along with the name of the method it becomes
+struct Procedure {
+    section: Option<Arc<str>>,
+    paragraph: Option<Arc<str>>,
+    method: String,
+}
+
+// every paragraph in the order they appear, which is what PERFORM ... THRU runs through. with
+// dispatch set a paragraph is run through the dispatch loop by its index in here instead of being
+// called directly
+struct Procedures {
+    procedures: Vec<Procedure>,
+    dispatch: bool,
+}
+
+impl Procedures {
+    fn new(sections: &[Section], dispatch: bool) -> Self {
+        let mut procedures = vec![];
+        for section in sections {
+            for paragraph in &section.paragraphs {
+                // the same paragraph name can be used in more than one section, so the section is
+                // part of the method name. the double underscore keeps it apart from a paragraph
+                // outside of any section whose name has the same words, and is why the generated
+                // crate allows names that are not snake case
+                let method = match (&section.name, &paragraph.name) {
+                    (Some(section), Some(paragraph)) => {
+                        rust_name(&format!("{section}--{paragraph}"))
+                    }
+                    (None, Some(paragraph)) => rust_name(paragraph),
+                    (Some(section), None) => format!("{}_start", rust_name(section)),
+                    // start is a reserved word, so it can't clash with the name of a paragraph
+                    (None, None) => String::from("start"),
+                };
+                procedures.push(Procedure {
+                    section: section.name.clone(),
+                    paragraph: paragraph.name.clone(),
+                    method,
+                });
+            }
+        }
+
+        Self {
+            procedures,
+            dispatch,
+        }
+    }
+
+    fn is_section(&self, name: &str) -> bool {
+        self.procedures
+            .iter()
+            .any(|p| p.section.as_deref() == Some(name))
+    }
+
+    // the flow pass has qualified every performed paragraph that is in a section
+    fn is_named(procedure: &Procedure, name: &str) -> bool {
+        match (&procedure.section, &procedure.paragraph) {
+            (Some(section), Some(paragraph)) => {
+                name.split_once(" of ") == Some((paragraph, section))
+            }
+            (None, Some(paragraph)) => paragraph.as_ref() == name,
+            (_, None) => false,
+        }
+    }
+
+    // a section runs from its first paragraph up to and including its last one
+    fn range(&self, from: &str, thru: &str) -> (usize, usize) {
+        let start = self
+            .procedures
+            .iter()
+            .position(|p| Self::is_named(p, from) || p.section.as_deref() == Some(from))
+            .unwrap_or_else(|| panic!("perform of unknown procedure {from}"));
+        let end = self
+            .procedures
+            .iter()
+            .rposition(|p| Self::is_named(p, thru) || p.section.as_deref() == Some(thru))
+            .unwrap_or_else(|| panic!("perform of unknown procedure {thru}"));
+        if end < start {
+            panic!("perform {from} thru {thru} runs backwards");
+        }
+
+        (start, end)
+    }
+
+    fn generate_perform(&self, from: &str, thru: Option<&str>) -> String {
+        if self.dispatch {
+            let (start, end) = self.range(from, thru.unwrap_or(from));
+            return format!("self.perform({start}, {end});\n");
+        }
+
+        let Some(thru) = thru else {
+            if self.is_section(from) {
+                return format!("self.{}();\n", rust_name(from));
+            }
+            let procedure = self
+                .procedures
+                .iter()
+                .find(|p| Self::is_named(p, from))
+                .unwrap_or_else(|| panic!("perform of unknown procedure {from}"));
+            return format!("self.{}();\n", procedure.method);
+        };
+
+        let (start, end) = self.range(from, thru);
+        self.procedures[start..=end]
+            .iter()
+            .map(|p| format!("self.{}();\n", p.method))
+            .collect()
+    }
+
+    // runs the paragraphs from one index through another, following any jumps they return. a jump
+    // out of the range keeps going until the end of the program, like it would in cobol. a paragraph
+    // returns after when it leaves a section early, which counts as having finished that section's
+    // last paragraph
+    fn generate_dispatch(&self) -> String {
+        let arms: String = self
+            .procedures
+            .iter()
+            .enumerate()
+            .map(|(i, p)| format!("{} => self.{}(),\n", i, p.method))
+            .collect();
+
+        format!(
+            "\nfn dispatch(&mut self, procedure: usize) -> Option<Next> {{\nmatch procedure {{\n{}_ => unreachable!(),\n}}\n}}\n\nfn perform(&mut self, from: usize, thru: usize) {{\nlet mut procedure = from;\nloop {{\nlet finished = match self.dispatch(procedure) {{\nSome(Next::GoTo(next)) => {{\nprocedure = next;\ncontinue;\n}}\nSome(Next::After(last)) => last,\nNone => procedure,\n}};\nif finished == thru || finished == {} {{\nbreak;\n}}\nprocedure = finished + 1;\n}}\n}}\n",
+            arms,
+            self.procedures.len() - 1
+        )
+    }
+}
+
+// the exit perform statements that belong to an inline perform, which are the ones in its body
+// that aren't inside of another inline perform. exit perform breaks out of a block around the whole
+// loop, and exit perform cycle out of a block around the body, so the loop's own test and
+// increment still run
+struct PerformExits {
+    depth: Option<usize>,
+    leave: bool,
+    cycle: bool,
+}
+
+impl PerformExits {
+    fn new(insts: &[Instruction]) -> Self {
+        let mut exits = Self {
+            depth: None,
+            leave: false,
+            cycle: false,
+        };
+        exits.find(insts);
+        exits
+    }
+
+    fn find(&mut self, insts: &[Instruction]) {
+        for inst in insts {
+            match inst {
+                Instruction::ExitPerform { cycle, depth } => {
+                    self.depth = Some(*depth);
+                    if *cycle {
+                        self.cycle = true;
+                    } else {
+                        self.leave = true;
+                    }
+                }
+                Instruction::If {
+                    then, otherwise, ..
+                } => {
+                    self.find(then);
+                    self.find(otherwise);
+                }
+                Instruction::Evaluate { whens, other, .. } => {
+                    for when in whens {
+                        self.find(&when.insts);
+                    }
+                    self.find(other);
+                }
+                Instruction::Sentence(insts) => self.find(insts),
+                Instruction::Add(Arithmetic { size_error, .. })
+                | Instruction::Subtract(Arithmetic { size_error, .. })
+                | Instruction::Multiply(Arithmetic { size_error, .. })
+                | Instruction::Divide {
+                    arithmetic: Arithmetic { size_error, .. },
+                    ..
+                }
+                | Instruction::Compute { size_error, .. } => {
+                    for handler in size_error.handlers() {
+                        self.find(handler);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn label_body(&self, body: String) -> String {
+        match self.depth {
+            Some(depth) if self.cycle => format!("'cycle_{}: {{\n{}}}\n", depth, body),
+            _ => body,
+        }
+    }
+
+    fn label_perform(&self, perform: String) -> String {
+        match self.depth {
+            Some(depth) if self.leave => format!("'perform_{}: {{\n{}}}\n", depth, perform),
+            _ => perform,
+        }
+    }
+}
+
+fn translate_core(instructions: Vec<Instruction>, procedures: &Procedures) -> String {
     let mut operations = String::new();
 
     for inst in instructions {
         match inst {
-            Instruction::Move(infix) => {
-                let (operation_text, possible_idents) = generate_move(infix);
-
-                operations += operation_text.as_str();
-                for ident in possible_idents {
-                    if !defined_variables.contains(&ident) {
-                        defined_variables.push(ident);
-                    }
+            Instruction::Move(moves) => {
+                for infix in moves {
+                    operations += &generate_move(infix);
                 }
             }
-            Instruction::Add(infix) => {
-                let operation_text = generate_add(infix);
-                operations += operation_text.as_str();
+            Instruction::Initialize(moves) => {
+                for infix in moves {
+                    operations += &generate_every_entry(infix);
+                }
             }
-            Instruction::Multiply(infix) => {
-                let operation_text = generate_multiply(infix);
-                operations += operation_text.as_str();
+            Instruction::Add(arithmetic) => {
+                let checked = arithmetic.size_error.is_checked();
+                let body = generate_add(&arithmetic.sources, arithmetic.receiving, checked);
+                operations += &generate_size_error(body, arithmetic.size_error, procedures);
+            }
+            Instruction::Multiply(arithmetic) => {
+                let checked = arithmetic.size_error.is_checked();
+                let body = generate_multiply(&arithmetic.sources, arithmetic.receiving, checked);
+                operations += &generate_size_error(body, arithmetic.size_error, procedures);
+            }
+            Instruction::Subtract(arithmetic) => {
+                let checked = arithmetic.size_error.is_checked();
+                let body = generate_subtract(&arithmetic.sources, arithmetic.receiving, checked);
+                operations += &generate_size_error(body, arithmetic.size_error, procedures);
+            }
+            Instruction::Divide {
+                arithmetic,
+                remainder,
+            } => {
+                let checked = arithmetic.size_error.is_checked();
+                let body = generate_divide(
+                    &arithmetic.sources,
+                    arithmetic.receiving,
+                    remainder,
+                    checked,
+                );
+                operations += &generate_size_error(body, arithmetic.size_error, procedures);
+            }
+            Instruction::Compute {
+                receivers,
+                expression,
+                size_error,
+            } => {
+                let body = generate_compute(&receivers, &expression, size_error.is_checked());
+                operations += &generate_size_error(body, size_error, procedures);
             }
             Instruction::Print(values) => {
-                let mut print_string = String::from("println!(\"");
-                let mut arguments_string = String::new();
-                for v in values {
-                    print_string += "{}";
-                    arguments_string += format!(", format!(\"{{}}\", {})", v).as_str();
-                }
-
-                print_string += "\"";
-                arguments_string += ");\n";
-                operations += format!("{}{}", print_string, arguments_string).as_str();
+                // a numeric field is displayed by its digits and sign, anything else by its bytes
+                let operands: Vec<String> = values
+                    .iter()
+                    .map(|value| match value {
+                        Value::Identifier(
+                            ident @ Ident {
+                                kind: IdentifierType::Numeric(_),
+                                ..
+                            },
+                        ) => format!("format!(\"{{}}\", {}).as_bytes()", ident),
+                        Value::Identifier(ident) => ident.bytes(),
+                        Value::String(_) | Value::Figurative(_) => format!("&{}[..]", value),
+                        value => format!("&{}[..]", byte_string(value.to_string().as_bytes())),
+                    })
+                    .collect();
+                operations += &format!("display(&[{}]);\n", operands.join(", "));
             }
             Instruction::Repeat {
-                left,
                 condition,
-                right,
+                test_after,
                 insts,
             } => {
-                let operation_text = generate_repeat(left, condition, right);
-                let (defined, instruction_text) = translate_core(insts);
-                for ident in defined {
-                    if !defined_variables.contains(&ident) {
-                        defined_variables.push(ident);
-                    }
+                let exits = PerformExits::new(&insts);
+                let instruction_text = exits.label_body(translate_core(insts, procedures));
+                operations +=
+                    &exits.label_perform(generate_repeat(condition, test_after, instruction_text));
+            }
+            Instruction::Varying {
+                varyings,
+                test_after,
+                insts,
+            } => {
+                let exits = PerformExits::new(&insts);
+                let instruction_text = exits.label_body(translate_core(insts, procedures));
+                operations +=
+                    &exits.label_perform(generate_varying(&varyings, test_after, instruction_text));
+            }
+            Instruction::Times { count, insts } => {
+                let exits = PerformExits::new(&insts);
+                let instruction_text = exits.label_body(translate_core(insts, procedures));
+                operations += &exits.label_perform(generate_times(count, instruction_text));
+            }
+            Instruction::Inline(insts) => {
+                let exits = PerformExits::new(&insts);
+                let instruction_text = exits.label_body(translate_core(insts, procedures));
+                operations += &exits.label_perform(format!("{{\n{}}}\n", instruction_text));
+            }
+            Instruction::If {
+                condition,
+                then,
+                otherwise,
+            } => {
+                let then_text = translate_core(then, procedures);
+                operations += &format!("if {} {{\n{}}}", generate_condition(condition), then_text);
+
+                if otherwise.is_empty() {
+                    operations += "\n";
+                } else {
+                    let otherwise_text = translate_core(otherwise, procedures);
+                    operations += &format!(" else {{\n{}}}\n", otherwise_text);
                 }
-                operations += operation_text.as_str();
-                operations += instruction_text.as_str();
-                operations += "}\n"
+            }
+            Instruction::Evaluate {
+                subjects,
+                whens,
+                other,
+            } => {
+                operations += &if can_match(&subjects, &whens) {
+                    generate_match(subjects, whens, other, procedures)
+                } else {
+                    generate_if_chain(subjects, whens, other, procedures)
+                };
+            }
+            Instruction::Perform { from, thru } => {
+                operations += &procedures.generate_perform(&from, thru.as_deref());
+            }
+            Instruction::SetTrue(conditions) => {
+                for condition in conditions {
+                    let Condition::Named { name, parent } = condition else {
+                        unreachable!()
+                    };
+                    operations += &format!(
+                        "self.set_{}({});\n",
+                        rust_name(&name),
+                        condition_entry(&parent)
+                    );
+                }
+            }
+            Instruction::GoTo { .. } => {
+                unreachable!("go to is replaced by the flow pass")
+            }
+            Instruction::ExitParagraph if procedures.dispatch => {
+                operations += "return None;\n";
+            }
+            Instruction::ExitParagraph => {
+                operations += "return;\n";
+            }
+            Instruction::RestartParagraph => {
+                operations += "continue 'paragraph;\n";
+            }
+            Instruction::ParagraphLoop(insts) => {
+                let instruction_text = translate_core(insts, procedures);
+                operations += &format!("'paragraph: loop {{\n{}break;\n}}\n", instruction_text);
+            }
+            Instruction::Jump(procedure) => {
+                operations += &format!("return Some(Next::GoTo({}));\n", procedure);
+            }
+            Instruction::Leave(procedure) => {
+                operations += &format!("return Some(Next::After({}));\n", procedure);
+            }
+            Instruction::ExitSection => {
+                unreachable!("exit section is replaced by the flow pass")
+            }
+            Instruction::ExitPerform {
+                cycle: false,
+                depth,
+            } => {
+                operations += &format!("break 'perform_{};\n", depth);
+            }
+            Instruction::ExitPerform { cycle: true, depth } => {
+                operations += &format!("break 'cycle_{};\n", depth);
+            }
+            Instruction::StopRun => {
+                operations += "self.stop_run();\n";
+            }
+            Instruction::Continue => {}
+            Instruction::NextSentence => {
+                operations += "break 'sentence;\n";
+            }
+            Instruction::Sentence(insts) => {
+                let instruction_text = translate_core(insts, procedures);
+                operations += &format!("'sentence: {{\n{}}}\n", instruction_text);
             }
         }
     }
 
-    (defined_variables, operations)
+    operations
+}
+
+// how many bytes of every group that ends with a table with occurs depending on are in use, which
+// is its fixed part and as many entries of the table as the item it depends on says
+fn generate_lengths(data: &[Data]) -> String {
+    let mut lengths = String::new();
+    walk_data(data, &mut vec![], &mut |ancestors, group| {
+        let Some(table) = group.depending_table().filter(|_| !group.is_filler()) else {
+            return;
+        };
+        let occurs = table.occurs.as_ref().unwrap();
+        let Count::Depending(count) = count_of(data, occurs) else {
+            unreachable!()
+        };
+        let entry = table.size() / occurs.max;
+        let path: Vec<Arc<str>> = ancestors.iter().map(|a| a.name.clone()).collect();
+        lengths += &format!(
+            "\nfn {}(&self) -> usize {{\nlet count = {}.decimal().truncate().clamp({}, {}) as usize;\n{} + count * {}\n}}\n",
+            length_accessor(&path, &group.name),
+            count,
+            occurs.min,
+            occurs.max,
+            group.size() - entry * occurs.max,
+            entry
+        );
+    });
+    lengths
+}
+
+// the structs generated for the groups of the working storage, and the condition name helpers of
+// every item in it
+#[derive(Default)]
+struct Storage {
+    types: String,
+    helpers: String,
+    struct_names: Vec<String>,
 }
 
-pub fn translate(data: Vec<Data>, instructions: Vec<Instruction>) -> String {
-    let (used_variables, operations) = translate_core(instructions);
-    let mut variable_definitions = String::new();
-    // let s = data.iter().fold(0, |acc, x| {
-    //     if let DataType::Picture(ident_type) = &x.data_type {
-    //         match ident_type {
-    //             IdentifierType::Numeric(size) | IdentifierType::Alphanumeric(size) => {
-    //                 if acc < *size {
-    //                     return *size;
-    //                 }
-    //             }
-    //             _ => {}
-    //         }
-    //     }
-    //
-    //     acc
-    // });
+impl Storage {
+    // the fields and their initial values for the items on one level of the hierarchy, which
+    // are in the groups named in path, along with the accessors of the items on it that have no
+    // storage of their own. views holds the positions in path of groups that are such items, and
+    // tables the tables the items are in, whose entries their condition names take. a table is an
+    // array of its entries
+    fn generate_items(
+        &mut self,
+        items: &[Data],
+        path: &[Arc<str>],
+        views: &[usize],
+        tables: &[Subscript],
+    ) -> (String, String, String) {
+        let mut fields = String::new();
+        let mut initializers = String::new();
+        let mut accessors = String::new();
+        let mut fillers = 0;
+
+        for data in items {
+            let name = if data.is_filler() {
+                fillers += 1;
+                format!("filler_{}", fillers)
+            } else {
+                rust_name(&data.name)
+            };
 
-    for var in data {
-        let type_str = match var.data_type {
-            DataType::Picture(ident_type) => match ident_type {
-                IdentifierType::Numeric(s) => {
-                    format!(": Num<{}> = Num::zero();", s)
+            let mut views = views.to_vec();
+            if data.is_view() {
+                views.push(path.len());
+            }
+            let mut tables = tables.to_vec();
+            if let Some(occurs) = &data.occurs {
+                tables.push(Subscript {
+                    position: path.len(),
+                    index: Index::Every,
+                    count: Count::Fixed(occurs.max),
+                });
+            }
+            let (field_type, initial) = match &data.data_type {
+                DataType::Picture(kind) => {
+                    let initial = match kind {
+                        IdentifierType::Numeric(_) => "Num::zero()",
+                        _ => "Text::spaces()",
+                    };
+                    (field_type(kind, data.usage), String::from(initial))
                 }
-                IdentifierType::Alphanumeric(s) => {
-                    format!("= String::from(\"{}\")", "0".repeat(s as usize))
+                DataType::Group => match &data.renames {
+                    Some(renames) => (format!("Text<{}>", renames.length), String::new()),
+                    None => {
+                        let struct_name = self.generate_group(data, path, &views, &tables);
+                        let initial = format!("{}::new()", struct_name);
+                        (struct_name, initial)
+                    }
+                },
+            };
+
+            // a value clause is applied to every entry of a table
+            let numeric = matches!(data.kind(), IdentifierType::Numeric(_));
+            let initial = match &data.value {
+                None => initial,
+                Some(Value::Figurative(Figurative::Zero)) if numeric => initial,
+                Some(Value::Figurative(figurative)) => {
+                    format!("{}.filled({})", initial, figurative_pattern(figurative))
                 }
-                _ => unreachable!(),
-            },
-            _ => unimplemented!(),
-        };
-        if used_variables.contains(&var.name) {
-            variable_definitions += &format!("let mut {}{};\n", var.name, type_str);
-        } else {
-            variable_definitions += &format!("let {}{};\n", var.name, type_str);
+                Some(Value::String(text)) => {
+                    format!("{}.with_text({})", initial, byte_string(text.as_bytes()))
+                }
+                Some(value) => format!("Num::from({})", decimal_operand(value)),
+            };
+            let (field_type, initial) = match &data.occurs {
+                Some(occurs) => (
+                    format!("[{}; {}]", field_type, occurs.max),
+                    format!("[{}; {}]", initial, occurs.max),
+                ),
+                None => (field_type, initial),
+            };
+
+            let parent = Ident {
+                name: data.name.clone(),
+                kind: data.kind(),
+                path: path.to_vec(),
+                subscripts: tables,
+                views,
+                justified: data.justified,
+                variable: data.depending_table().is_some(),
+            };
+            for condition in &data.conditions {
+                self.helpers += "\n";
+                self.helpers += &generate_condition_helpers(condition, &parent);
+            }
+
+            // an item that redefines another is a view of the bytes of that one, and an item that
+            // renames others a view of the bytes of the record they are in
+            let (storage, offset) = match (&data.redefines, &data.renames) {
+                (Some(redefined), _) => {
+                    let redefined_item = items.iter().find(|item| item.name == *redefined);
+                    let accessor = redefined_item.is_some_and(|item| item.is_view());
+                    (Some((rust_name(redefined), accessor)), 0)
+                }
+                (None, Some(renames)) => (None, renames.offset),
+                (None, None) => {
+                    fields += &format!("{}: {},\n", name, field_type);
+                    initializers += &format!("{}: {},\n", name, initial);
+                    continue;
+                }
+            };
+            if data.is_filler() {
+                continue;
+            }
+            let [shared, mutable] = ["", "_mut"].map(|suffix| match &storage {
+                Some((redefined, true)) => {
+                    format!("self.{redefined}{suffix}().view{suffix}({offset})")
+                }
+                Some((redefined, false)) => format!("self.{redefined}.view{suffix}({offset})"),
+                None => format!("self.view{suffix}({offset})"),
+            });
+            accessors += &format!(
+                "\nfn {name}(&self) -> &{field_type} {{\n{shared}\n}}\n\nfn {name}_mut(&mut self) -> &mut {field_type} {{\n{mutable}\n}}\n"
+            );
+        }
+
+        (fields, initializers, accessors)
+    }
+
+    // a group is a struct of its items, laid out one after another without any padding so that
+    // its bytes are those of the items. it displays as those bytes, like an alphanumeric field
+    fn generate_group(
+        &mut self,
+        group: &Data,
+        path: &[Arc<str>],
+        views: &[usize],
+        tables: &[Subscript],
+    ) -> String {
+        let mut path = path.to_vec();
+        path.push(group.name.clone());
+        let (fields, initializers, accessors) =
+            self.generate_items(&group.children, &path, views, tables);
+
+        let name = self.struct_name(&group.name);
+        self.types += &format!(
+            "\n#[repr(C)]\n#[derive(Clone, Copy)]\nstruct {name} {{\n{fields}}}\n\nimpl {name} {{\nfn new() -> Self {{\nSelf {{\n{initializers}}}\n}}\n{accessors}}}\n\nunsafe impl Record for {name} {{}}\n\nimpl std::fmt::Display for {name} {{\nfn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{\nwrite!(f, \"{{}}\", self.alphanumeric())\n}}\n}}\n"
+        );
+
+        name
+    }
+
+    // the name of the group in upper camel case. groups of the same name in different records,
+    // and groups named after a type the program already uses, are told apart by a number
+    fn struct_name(&mut self, group: &str) -> String {
+        let base: String = group
+            .split('-')
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let mut chars = part.chars();
+                let first = chars.next().unwrap().to_ascii_uppercase();
+                std::iter::once(first).chain(chars).collect::<String>()
+            })
+            .collect();
+
+        let reserved = [
+            "Alphanumeric",
+            "Decimal",
+            "Next",
+            "Num",
+            "Program",
+            "Record",
+            "Rounding",
+            "Text",
+        ];
+        let mut name = base.clone();
+        let mut count = 1;
+        while reserved.contains(&name.as_str()) || self.struct_names.contains(&name) {
+            count += 1;
+            name = format!("{}{}", base, count);
         }
+
+        self.struct_names.push(name.clone());
+        name
     }
+}
 
-    return format!(
-        "#![allow(unused)]\n\nuse conum::{{Num, NumFrom}};\nfn main() {{\n{}\n{}}}",
-        variable_definitions, operations
-    );
+// the working storage becomes the fields of a program struct, and every paragraph and section a
+// method on it, so they all share the same storage
+pub fn translate(data: Vec<Data>, flow: Flow) -> String {
+    let Flow { sections, dispatch } = flow;
+    let procedures = Procedures::new(&sections, dispatch);
+    let mut storage = Storage::default();
+    let (fields, initializers, accessors) = storage.generate_items(&data, &[], &[], &[]);
+    let Storage {
+        mut types,
+        mut helpers,
+        ..
+    } = storage;
+    helpers += &generate_lengths(&data);
+
+    let mut run = String::new();
+    let mut methods = String::new();
+    if dispatch {
+        for (paragraph, procedure) in sections
+            .into_iter()
+            .flat_map(|s| s.paragraphs)
+            .zip(&procedures.procedures)
+        {
+            let body = translate_core(paragraph.insts, &procedures);
+            methods += &format!(
+                "\nfn {}(&mut self) -> Option<Next> {{\n{}None\n}}\n",
+                procedure.method, body
+            );
+        }
+        if !procedures.procedures.is_empty() {
+            run += &format!("self.perform(0, {});\n", procedures.procedures.len() - 1);
+        }
+        methods += &procedures.generate_dispatch();
+        types += "\nenum Next {\nGoTo(usize),\nAfter(usize),\n}\n";
+    } else {
+        let mut procedure = procedures.procedures.iter();
+        for section in sections {
+            if let Some(section_name) = &section.name {
+                run += &format!("self.{}();\n", rust_name(section_name));
+            }
+
+            let mut section_body = String::new();
+            for paragraph in section.paragraphs {
+                let body = translate_core(paragraph.insts, &procedures);
+                let method = &procedure.next().unwrap().method;
+
+                methods += &format!("\nfn {}(&mut self) {{\n{}}}\n", method, body);
+                let call = format!("self.{}();\n", method);
+                if section.name.is_some() {
+                    section_body += &call;
+                } else {
+                    run += &call;
+                }
+            }
+
+            if let Some(section_name) = &section.name {
+                methods += &format!(
+                    "\nfn {}(&mut self) {{\n{}}}\n",
+                    rust_name(section_name),
+                    section_body
+                );
+            }
+        }
+    }
+
+    // falling off the end of the procedure division stops the run just like stop run does
+    let stop_run = "\nfn stop_run(&self) -> ! {\nstd::process::exit(self.return_code.decimal().truncate() as i32);\n}\n";
+
+    format!(
+        "#![allow(unused, non_snake_case)]\n\nuse cello_runtime::{{de_edit, display, edit_alphanumeric, edit_numeric, figurative, subscript, usage, Alphanumeric, Decimal, Num, Record, Rounding, Text}};\n\nstruct Program {{\n{}}}\n{}\nfn main() {{\nlet mut program = Program {{\n{}}};\nprogram.run();\nprogram.stop_run();\n}}\n\nimpl Program {{\nfn run(&mut self) {{\n{}}}\n{}{}{}{}}}\n",
+        fields, types, initializers, run, stop_run, methods, accessors, helpers
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{flow, parser::Parser, translate::translate};
+
+    // the rust a program is translated into, before it is formatted
+    fn transpile(procedure: &str) -> String {
+        transpile_with("77 b pic 9.\n77 i pic 9.", procedure)
+    }
+
+    fn transpile_with(storage: &str, procedure: &str) -> String {
+        let source = format!(
+            "identification division.
+program-id. test.
+data division.
+working-storage section.
+{storage}
+procedure division.
+{procedure}"
+        );
+        let (data, sections) = Parser::new(&source).parse();
+        translate(data, flow::recover(sections))
+    }
+
+    #[test]
+    fn test_go_to_in_size_error() {
+        let program = transpile(
+            "first-para.
+    add 1 to b on size error go to third-para end-add.
+second-para.
+    display b.
+third-para.
+    stop run.",
+        );
+        assert!(program.contains("if size_error {\nreturn Some(Next::GoTo(2));\n}"));
+    }
+
+    #[test]
+    fn test_exit_section_in_size_error() {
+        let program = transpile(
+            "main section.
+first-para.
+    add 1 to b on size error exit section end-add.
+second-para.
+    display b.
+other section.
+third-para.
+    stop run.",
+        );
+        assert!(program.contains("if size_error {\nreturn Some(Next::After(1));\n}"));
+    }
+
+    #[test]
+    fn test_exit_perform_in_size_error() {
+        let program = transpile(
+            "perform varying i from 1 by 1 until i > 3
+    add 1 to b on size error exit perform end-add
+end-perform.
+stop run.",
+        );
+        assert!(program.contains("'perform_1: {"));
+        assert!(program.contains("if size_error {\nbreak 'perform_1;\n}"));
+    }
+
+    #[test]
+    fn test_paragraph_in_two_sections() {
+        let program = transpile(
+            "main section.
+    perform p of s1.
+    perform p in s2.
+    stop run.
+s1 section.
+p.
+    display b.
+s2 section.
+p.
+    display i.",
+        );
+        assert!(program.contains("fn s1__p(&mut self)"));
+        assert!(program.contains("fn s2__p(&mut self)"));
+        assert!(program.contains("self.s1__p();\nself.s2__p();\n"));
+    }
+
+    #[test]
+    fn test_unqualified_paragraph_in_own_section() {
+        let program = transpile(
+            "s1 section.
+p.
+    display b.
+q.
+    perform p.
+s2 section.
+p.
+    display i.",
+        );
+        assert!(program.contains("fn s1__q(&mut self) {\nself.s1__p();\n}"));
+    }
+
+    #[test]
+    #[should_panic(expected = "procedure p needs to be qualified by its section")]
+    fn test_ambiguous_paragraph() {
+        transpile(
+            "main section.
+    perform p.
+s1 section.
+p.
+    display b.
+s2 section.
+p.
+    display i.",
+        );
+    }
+
+    #[test]
+    fn test_condition_name_in_table() {
+        let program = transpile_with(
+            "01 flags.
+    05 flag pic x occurs 3 times.
+        88 flag-on value \"y\".
+77 i pic 9.",
+            "set flag-on(2) to true.
+if flag-on(i) stop run.",
+        );
+        assert!(program.contains("fn flag_on(&self, entry_1: usize) -> bool {"));
+        assert!(program.contains("fn set_flag_on(&mut self, entry_1: usize) {"));
+        assert!(
+            program.contains("self.set_flag_on(subscript(Decimal::from(2), Decimal::from(3)));")
+        );
+        assert!(
+            program.contains("if self.flag_on(subscript(self.i.decimal(), Decimal::from(3))) {")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "flag-on needs a subscript for every table it is in")]
+    fn test_condition_name_in_table_without_subscript() {
+        transpile_with(
+            "01 flags.
+    05 flag pic x occurs 3 times.
+        88 flag-on value \"y\".",
+            "if flag-on stop run.",
+        );
+    }
+
+    #[test]
+    fn test_go_to_next_paragraph() {
+        let program = transpile(
+            "first-para.
+    go to second-para.
+second-para.
+    stop run.",
+        );
+        assert!(program.contains("fn first_para(&mut self) {\nreturn;\n}"));
+    }
+
+    #[test]
+    fn test_go_to_next_paragraph_performed() {
+        let program = transpile(
+            "main-para.
+    perform first-para.
+first-para.
+    go to second-para.
+second-para.
+    stop run.",
+        );
+        assert!(program.contains("return Some(Next::GoTo(2));"));
+    }
+
+    #[test]
+    fn test_depending_on_length() {
+        let program = transpile_with(
+            "77 n pic 9.
+01 odo.
+    05 head pic x.
+    05 items pic xx occurs 1 to 5 times depending on n.",
+            "display odo.",
+        );
+        assert!(program.contains(
+            "fn odo_length(&self) -> usize {\nlet count = self.n.decimal().truncate().clamp(1, 5) as usize;\n1 + count * 2\n}"
+        ));
+        assert!(program.contains("display(&[self.odo.bytes_to(self.odo_length())]);"));
+    }
+
+    #[test]
+    fn test_literal_bytes() {
+        let program = transpile_with(
+            "77 a pic x(3) value \"a\\b\".",
+            "display high-values \"\\n\" a.",
+        );
+        assert!(program.contains(".with_text(b\"a\\\\b\")"));
+        assert!(program.contains("display(&[&b\"\\xff\"[..], &b\"\\\\n\"[..], self.a.bytes()]);"));
+    }
+
+    #[test]
+    fn test_condition_precedence() {
+        let program = transpile("if b > 1 or b = 2 and not i < 3 display \"x\" end-if.");
+        assert!(program.contains(
+            "if self.b.decimal() > Decimal::from(1) || (self.b.decimal() == Decimal::from(2) && !(self.i.decimal() < Decimal::from(3))) {"
+        ));
+    }
+
+    #[test]
+    fn test_abbreviated_relation() {
+        let program = transpile("if b > 1 or = 3 display \"x\" end-if.");
+        assert!(program.contains(
+            "if self.b.decimal() > Decimal::from(1) || self.b.decimal() == Decimal::from(3) {"
+        ));
+        let program = transpile("if b not = 1 and 2 display \"x\" end-if.");
+        assert!(program.contains(
+            "if self.b.decimal() != Decimal::from(1) && self.b.decimal() != Decimal::from(2) {"
+        ));
+    }
+
+    #[test]
+    fn test_not_condition() {
+        let program = transpile("if not (b = 1 or i = 2) display \"x\" end-if.");
+        assert!(program.contains(
+            "if !(self.b.decimal() == Decimal::from(1) || self.i.decimal() == Decimal::from(2)) {"
+        ));
+    }
+
+    #[test]
+    fn test_expression_precedence() {
+        let program = transpile("compute b = 1 + 2 * 3 - i / 2 ** 2.");
+        assert!(program.contains(
+            "Some((Decimal::new(1, 0) + (Decimal::new(2, 0) * Decimal::new(3, 0))) - (self.i.decimal().divide((Decimal::new(2, 0).power(Decimal::new(2, 0))?))?))"
+        ));
+        let program = transpile("compute b = (1 + 2) * -i.");
+        assert!(program.contains(
+            "let result = (Decimal::new(1, 0) + Decimal::new(2, 0)) * (-self.i.decimal());"
+        ));
+    }
+
+    #[test]
+    fn test_add_to_each_receiver() {
+        let program = transpile("add 1 2 to b i.");
+        assert!(program.contains(
+            "let addend = (Decimal::from(1) + Decimal::from(2));\nself.b.set(self.b.decimal() + addend);\nself.i.set(self.i.decimal() + addend);"
+        ));
+        let program = transpile("add b 2 to i giving b.");
+        assert!(program.contains(
+            "let sum = (self.b.decimal() + Decimal::from(2)) + self.i.decimal();\nself.b.set(sum);"
+        ));
+    }
+
+    #[test]
+    fn test_multiply_giving() {
+        let program = transpile("multiply 2 by b i.");
+        assert!(program.contains(
+            "let multiplier = Decimal::from(2);\nself.b.set(multiplier * self.b.decimal());\nself.i.set(multiplier * self.i.decimal());"
+        ));
+        let program = transpile("multiply b by 3 giving i b.");
+        assert!(program.contains(
+            "let product = multiplier * Decimal::from(3);\nself.i.set(product);\nself.b.set(product);"
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_match() {
+        let program = transpile_with(
+            "77 t pic x(3).\n77 u pic x(3).",
+            "evaluate t also u when \"ab\" also \"x\" display \"a\" when \"cd\" also any display \"b\" when other display \"c\" end-evaluate.",
+        );
+        assert!(program.contains(
+            "match (self.t.alphanumeric().trim_end(), self.u.alphanumeric().trim_end()) {\n(b\"ab\", b\"x\") => {\ndisplay(&[&b\"a\"[..]]);\n}\n(b\"cd\", _) => {\ndisplay(&[&b\"b\"[..]]);\n}\n_ => {\ndisplay(&[&b\"c\"[..]]);\n}\n}"
+        ));
+    }
+
+    #[test]
+    fn test_evaluate_if_chain() {
+        let program = transpile(
+            "evaluate b also true when 1 also i > 2 display \"a\" when 2 thru 4 also any display \"b\" when other display \"c\" end-evaluate.",
+        );
+        assert!(program.contains(
+            "if self.b.decimal() == Decimal::from(1) && self.i.decimal() > Decimal::from(2) {\ndisplay(&[&b\"a\"[..]]);\n} else if self.b.decimal() >= Decimal::from(2) && self.b.decimal() <= Decimal::from(4) {\ndisplay(&[&b\"b\"[..]]);\n} else {\ndisplay(&[&b\"c\"[..]]);\n}"
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot move non-integer numeric item d to alphanumeric item t")]
+    fn test_move_fraction_to_alphanumeric() {
+        transpile_with("77 d pic 9v9.\n77 t pic x(3).", "move d to t.");
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot move numeric literal 5 to alphabetic item a")]
+    fn test_move_number_to_alphabetic() {
+        transpile_with("77 a pic a(3).", "move 5 to a.");
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot move space to numeric item b")]
+    fn test_move_space_to_numeric() {
+        transpile("move spaces to b.");
+    }
 }